@@ -100,7 +100,7 @@ mod benchmarks {
         c.bench_function("dbs::reset", move |b| b.iter(|| bs1.reset()));
     }
     pub fn bench_all_dbs(c: &mut Criterion) {
-        let bs = DenseBitSet::from_integer(u64::max_value());
+        let bs = DenseBitSet::from_integer(u64::MAX);
         c.bench_function("dbs::all", move |b| b.iter(|| bs.all()));
     }
 
@@ -176,6 +176,37 @@ mod benchmarks {
         c.bench_function("dbs::xor", move |b| b.iter(|| bs1 ^ bs2));
     }
 
+    pub fn bench_pairwise_hamming_dbs(c: &mut Criterion) {
+        let sets: Vec<DenseBitSet> = (0..32)
+            .map(|i| DenseBitSet::from_integer(1234567890 * i))
+            .collect();
+
+        c.bench_function("dbs::pairwise_hamming", move |b| {
+            b.iter(|| DenseBitSet::pairwise_hamming(&sets))
+        });
+    }
+
+    pub fn bench_intersection_counts_dbse(c: &mut Criterion) {
+        let query: DenseBitSetExtended = (0..4096).filter(|p| p % 3 == 0).collect();
+        let candidates: Vec<DenseBitSetExtended> = (1..1000)
+            .map(|k| (0..4096).filter(|p| p % (k % 64 + 1) == 0).collect())
+            .collect();
+
+        c.bench_function("dbse::intersection_counts", move |b| {
+            b.iter(|| query.intersection_counts(&candidates))
+        });
+    }
+
+    #[cfg(feature = "rayon")]
+    pub fn bench_par_iter_ones_dbse(c: &mut Criterion) {
+        use rayon::prelude::*;
+
+        let bs: DenseBitSetExtended = (0..60000).filter(|p| p % 7 == 0).collect();
+        c.bench_function("dbse::par_iter_ones", move |b| {
+            b.iter(|| bs.par_iter_ones().sum::<usize>())
+        });
+    }
+
 }
 
 criterion_group!(
@@ -199,9 +230,11 @@ criterion_group!(
     benchmarks::bench_get_bit_dbs,
     benchmarks::bench_hamming_weight_dbs,
     benchmarks::bench_insert_dbs,
+    benchmarks::bench_intersection_counts_dbse,
     benchmarks::bench_none_dbs,
     benchmarks::bench_none_dbse,
     benchmarks::bench_not_dbs,
+    benchmarks::bench_pairwise_hamming_dbs,
     benchmarks::bench_reset_dbs,
     benchmarks::bench_reverse_dbs,
     benchmarks::bench_reverse_dbse,
@@ -213,4 +246,10 @@ criterion_group!(
     benchmarks::bench_to_string_dbse,
 );
 
+#[cfg(feature = "rayon")]
+criterion_group!(rayon_benches, benchmarks::bench_par_iter_ones_dbse);
+
+#[cfg(not(feature = "rayon"))]
 criterion_main!(benches);
+#[cfg(feature = "rayon")]
+criterion_main!(benches, rayon_benches);
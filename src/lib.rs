@@ -7,11 +7,13 @@ mod vec64impl;
 
 pub use crate::bitset::BitSet;
 pub use crate::u64impl::DenseBitSet;
-pub use crate::vec64impl::DenseBitSetExtended;
+pub use crate::vec64impl::{Bits, DenseBitSetExtended, Side};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cmp::{max, min};
+    use std::hash::{Hash, Hasher};
 
     // Tests for data initialization methods
     // dbs : from_integer, from_string
@@ -108,7 +110,7 @@ mod tests {
 
     #[test]
     fn test_all_dbs() {
-        let mut bs = DenseBitSet::from_integer(u64::max_value());
+        let mut bs = DenseBitSet::from_integer(u64::MAX);
         assert!(bs.all());
         bs.set_bit(3, false);
         assert!(!bs.all());
@@ -158,6 +160,19 @@ mod tests {
         assert!(!bs.none());
     }
 
+    #[test]
+    fn test_any_none_non_canonical_dbse() {
+        // `subset` keeps the bits beyond the requested length in the storage
+        let dirty =
+            DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0xF000)).subset(0, 12);
+        assert_eq!(
+            dirty,
+            DenseBitSetExtended::from_string(String::from("000"), 16)
+        );
+        assert!(!dirty.any());
+        assert!(dirty.none());
+    }
+
     #[test]
     fn test_get_size_dbse() {
         let mut bs = DenseBitSetExtended::from_string(String::from("deadbeef"), 16);
@@ -441,7 +456,7 @@ mod tests {
     fn test_hamming_weight_dbs() {
         let bs1 = DenseBitSet::from_integer(0);
         let bs2 = DenseBitSet::from_integer(1234567890);
-        let bs3 = DenseBitSet::from_integer(u64::max_value());
+        let bs3 = DenseBitSet::from_integer(u64::MAX);
 
         assert_eq!(bs1.get_weight(), 0);
         assert_eq!(bs2.get_weight(), 12);
@@ -753,6 +768,2708 @@ mod tests {
         assert!(bs1.get_bit(54));
     }
 
+    #[test]
+    fn test_compress_dbs() {
+        let mask = DenseBitSet::from_integer(0b10101);
+        let bs1 = DenseBitSet::from_integer(0b10001);
+        let bs2 = DenseBitSet::from_integer(0b01110);
+        let bs3 = DenseBitSet::from_integer(u64::MAX);
+
+        assert_eq!(bs1.compress(mask).to_integer(), 0b101);
+        assert_eq!(bs2.compress(mask).to_integer(), 0b010);
+        assert_eq!(bs3.compress(mask).to_integer(), 0b111);
+        assert_eq!(bs3.compress(bs3), bs3);
+        assert!(bs3.compress(DenseBitSet::new()).none());
+    }
+
+    #[test]
+    fn test_equals_dense_dbse() {
+        let dbs = DenseBitSet::from_integer(1234567890);
+        let mut bs = DenseBitSetExtended::from_dense_bitset(dbs);
+        assert!(bs.equals_dense(dbs));
+
+        bs.set_bit(150, true);
+        assert!(!bs.equals_dense(dbs));
+
+        bs.set_bit(150, false);
+        assert!(bs.equals_dense(dbs));
+        assert!(!bs.equals_dense(DenseBitSet::from_integer(1234567891)));
+        assert!(DenseBitSetExtended::new().equals_dense(DenseBitSet::new()));
+
+        // Bits beyond the size of a non-canonical bitset are ignored, as with `==`
+        let dirty =
+            DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0xF000)).subset(0, 12);
+        assert_eq!(
+            dirty,
+            DenseBitSetExtended::from_string(String::from("000"), 16)
+        );
+        assert!(dirty.equals_dense(DenseBitSet::new()));
+
+        let mut dirty_words = DenseBitSetExtended::from_dense_bitset(dbs);
+        dirty_words.set_word(1, u64::MAX);
+        let dirty_words = dirty_words.subset(0, 64);
+        assert!(dirty_words.equals_dense(dbs));
+    }
+
+    #[test]
+    fn test_hashset_dbs() {
+        let positions: std::collections::HashSet<usize> =
+            [0, 3, 17, 42, 63].iter().cloned().collect();
+        let bs = DenseBitSet::from_hashset(&positions);
+
+        assert_eq!(bs.get_weight(), 5);
+        assert!(bs.get_bit(42));
+        assert_eq!(bs.to_hashset(), positions);
+        assert!(DenseBitSet::new().to_hashset().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_from_hashset_overflow_dbs() {
+        let positions: std::collections::HashSet<usize> = [3, 64].iter().cloned().collect();
+        let _bs = DenseBitSet::from_hashset(&positions); // Should panic: bit #64 is out of bounds
+    }
+
+    #[test]
+    fn test_sorted_positions_dbse() {
+        let positions = vec![0, 3, 63, 64, 127, 500, 1023];
+        let bs = DenseBitSetExtended::from_sorted_positions(&positions);
+
+        assert_eq!(bs.get_size(), 1024);
+        assert_eq!(bs.get_weight(), 7);
+        assert_eq!(bs.to_sorted_positions(), positions);
+        assert!(DenseBitSetExtended::from_sorted_positions(&[]).none());
+
+        // The largest position allowed by the size limit
+        let bs = DenseBitSetExtended::from_sorted_positions(&[7, 63999]);
+        assert_eq!(bs.get_size(), 64000);
+        assert_eq!(bs.to_sorted_positions(), vec![7, 63999]);
+
+        // Bits beyond the size of a non-canonical bitset are ignored
+        let dirty =
+            DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0xff)).subset(0, 4);
+        assert_eq!(dirty.to_sorted_positions(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_to_sorted_positions_dbse() {
+        let bs = DenseBitSetExtended::from_string(
+            String::from("f001eddadf411eddec0de5ca1ab1ec0feefeeb1e01dc0b01"),
+            16,
+        );
+        let positions = bs.to_sorted_positions();
+        let expected: Vec<u32> = (0..bs.get_size())
+            .filter(|&i| bs.get_bit(i))
+            .map(|i| i as u32)
+            .collect();
+
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(positions, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn catch_unsorted_positions_dbse() {
+        let _bs = DenseBitSetExtended::from_sorted_positions(&[5, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_sorted_positions_overflow_dbse() {
+        // Should panic: the position exceeds the size limit
+        let _bs = DenseBitSetExtended::from_sorted_positions(&[64000]);
+    }
+
+    #[test]
+    fn test_replicate_dbs() {
+        assert_eq!(
+            DenseBitSet::replicate(0b01, 2).to_integer(),
+            0x5555555555555555
+        );
+        assert_eq!(
+            DenseBitSet::replicate(0xF0, 8).to_integer(),
+            0xF0F0F0F0F0F0F0F0
+        );
+        assert_eq!(
+            DenseBitSet::replicate(0b1010, 4).to_integer(),
+            0xAAAAAAAAAAAAAAAA
+        );
+        assert_eq!(
+            DenseBitSet::replicate(0b001, 3).to_integer(),
+            0x9249249249249249
+        );
+        assert_eq!(
+            DenseBitSet::replicate(1234567890, 64).to_integer(),
+            1234567890
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_replicate_zero_width_dbs() {
+        let _bs = DenseBitSet::replicate(0b1, 0); // Should panic: zero-width pattern
+    }
+
+    #[test]
+    fn test_word_prefix_counts_dbse() {
+        let bs = DenseBitSetExtended::from_string(
+            String::from("f001eddadf411eddec0de5ca1ab1ec0feefeeb1e01dc0b01"),
+            16,
+        );
+        let counts = bs.word_prefix_counts();
+
+        assert_eq!(counts.len(), 4);
+        for (i, &c) in counts.iter().enumerate() {
+            let expected = (0..i * 64).filter(|&j| bs.get_bit(j)).count() as u32;
+            assert_eq!(c, expected);
+        }
+        assert_eq!(counts[3], bs.get_weight());
+        assert_eq!(DenseBitSetExtended::new().word_prefix_counts(), vec![0]);
+
+        // Bits beyond the size of a non-canonical bitset are ignored
+        let dirty = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(u64::MAX))
+            .subset(0, 12);
+        assert_eq!(dirty.word_prefix_counts(), vec![0, 12]);
+        assert_eq!(dirty.word_prefix_counts()[1], dirty.rank(64) as u32);
+
+        // Words beyond the storage are counted as zeros
+        let mut sparse = DenseBitSetExtended::new();
+        sparse.set_bit(200, false);
+        let counts = sparse.word_prefix_counts();
+        assert_eq!(counts, vec![0; 5]);
+        for (i, &c) in counts.iter().enumerate() {
+            assert_eq!(c, sparse.rank(i * 64) as u32);
+        }
+    }
+
+    #[test]
+    fn test_nth_set_bit_dbs() {
+        let bs = DenseBitSet::from_integer(1234567890);
+        assert_eq!(bs.nth_set_bit(0), Some(bs.first_set() as usize));
+        assert_eq!(bs.nth_set_bit(1), Some(4));
+        assert_eq!(bs.nth_set_bit(11), Some(30));
+        assert_eq!(bs.nth_set_bit(12), None);
+        assert_eq!(DenseBitSet::new().nth_set_bit(0), None);
+        assert_eq!(
+            DenseBitSet::from_integer(u64::MAX).nth_set_bit(63),
+            Some(63)
+        );
+    }
+
+    #[test]
+    fn test_get_set_word_dbse() {
+        let mut bs = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(42));
+        bs.set_word(3, 1234567890);
+
+        assert_eq!(bs.get_word(3), 1234567890);
+        assert_eq!(bs.get_word(0), 42);
+        assert_eq!(bs.get_word(1), 0);
+        assert_eq!(bs.get_word(50), 0);
+        assert_eq!(bs.get_size(), 256);
+        assert!(bs.get_bit(193));
+
+        bs.set_word(1, u64::MAX);
+        assert_eq!(bs.get_size(), 256);
+        assert_eq!(bs.get_weight(), 3 + 64 + 12);
+
+        // The last word allowed by the size limit
+        bs.set_word(999, 1);
+        assert_eq!(bs.get_size(), 64000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_set_word_too_large_dbse() {
+        let mut bs = DenseBitSetExtended::new();
+        bs.set_word(5000, 1); // Should panic: the bitset would exceed the size limit
+    }
+
+    #[test]
+    fn test_rank_dbs() {
+        let bs = DenseBitSet::from_integer(0xF0F0F0F0F0F0F0F0);
+        assert_eq!(bs.rank(0), 0);
+        assert_eq!(bs.rank(5), 1);
+        assert_eq!(bs.rank(32), 16);
+        assert_eq!(bs.rank(64), 32);
+        assert_eq!(bs.rank(65), 32);
+        assert_eq!(bs.rank(usize::MAX), 32);
+
+        for n in 0..32 {
+            let p = bs.select(n).unwrap();
+            assert_eq!(bs.rank(p), n);
+            assert_eq!(bs.rank(p + 1), n + 1);
+        }
+    }
+
+    #[test]
+    fn test_insert_u64_truncation_dbse() {
+        let mut bs = DenseBitSetExtended::new();
+        bs.set_word(2, 0);
+        bs.insert_u64(0xffff, 60, 8);
+        bs.insert_u64(0xff, 130, 4);
+
+        assert_eq!(bs.get_weight(), 12);
+        assert_eq!(bs.extract_u64(56, 16), 0x0ff0);
+        assert_eq!(bs.extract_u64(128, 16), 0b111100);
+        assert_eq!(bs.get_size(), 192);
+    }
+
+    #[test]
+    fn test_from_bool_dbs() {
+        assert_eq!(DenseBitSet::from_bool(true, 0).to_integer(), 1);
+        assert_eq!(DenseBitSet::from_bool(true, 63).to_integer(), 1 << 63);
+        assert!(DenseBitSet::from_bool(false, 0).none());
+        assert!(DenseBitSet::from_bool(false, 63).none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_from_bool_overflow_dbs() {
+        let _bs = DenseBitSet::from_bool(true, 64); // Should panic: bit #64 is out of bounds
+    }
+
+    #[test]
+    fn test_mask_if_dbs() {
+        let bs = DenseBitSet::from_integer(u64::MAX);
+        assert_eq!(bs.mask_if(true), bs);
+        assert!(bs.mask_if(false).none());
+        assert!(DenseBitSet::new().mask_if(true).none());
+    }
+
+    #[test]
+    fn test_iter_runs_dbse() {
+        let mut bs = DenseBitSetExtended::new();
+        bs.insert_u64(u64::MAX, 60, 10);
+        bs.insert_u64(u64::MAX, 120, 64);
+        bs.insert_u64(u64::MAX, 184, 8);
+        bs.set_bit(0, true);
+        bs.set_bit(250, true);
+        bs.set_bit(255, true);
+
+        let mut expected = vec![];
+        let mut start = None;
+        for i in 0..=bs.get_size() {
+            let b = i < bs.get_size() && bs.get_bit(i);
+            match (b, start) {
+                (true, None) => start = Some(i),
+                (false, Some(s)) => {
+                    expected.push((s, i - s));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        let runs: Vec<(usize, usize)> = bs.iter_runs().collect();
+        assert_eq!(runs, vec![(0, 1), (60, 10), (120, 72), (250, 1), (255, 1)]);
+        assert_eq!(runs, expected);
+        assert_eq!(DenseBitSetExtended::new().iter_runs().count(), 0);
+    }
+
+    #[test]
+    fn test_wrapping_neg_dbs() {
+        for &v in &[0, 1, 1234567890, 1 << 63, u64::MAX] {
+            let bs = DenseBitSet::from_integer(v);
+            assert_eq!(bs.wrapping_neg().to_integer(), v.wrapping_neg());
+            assert_eq!(bs.wrapping_neg().wrapping_neg(), bs);
+        }
+    }
+
+    #[test]
+    fn test_abs_diff_dbs() {
+        let bs1 = DenseBitSet::from_integer(1234567890);
+        let bs2 = DenseBitSet::from_integer(u64::MAX);
+
+        assert_eq!(bs1.abs_diff(bs2).to_integer(), u64::MAX - 1234567890);
+        assert_eq!(bs2.abs_diff(bs1), bs1.abs_diff(bs2));
+        assert!(bs1.abs_diff(bs1).none());
+    }
+
+    #[test]
+    fn test_grow_words_dbse() {
+        let mut bs = DenseBitSetExtended::new();
+        bs.grow_words(3);
+        assert_eq!(bs.get_size(), 192);
+        assert!(bs.none());
+        assert!((0..192).all(|i| !bs.get_bit(i)));
+
+        bs.set_bit(150, true);
+        bs.grow_words(2);
+        assert_eq!(bs.get_size(), 192);
+        assert!(bs.get_bit(150));
+
+        bs.grow_words(1000);
+        assert_eq!(bs.get_size(), 64000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_grow_words_too_large_dbse() {
+        let mut bs = DenseBitSetExtended::new();
+        bs.grow_words(1001); // Should panic: the bitset would exceed the size limit
+    }
+
+    #[test]
+    fn test_const_dbs() {
+        const MASK: DenseBitSet = DenseBitSet::from_integer(0xff);
+        const LOW: u64 = MASK.extract(0, 4);
+        const WEIGHT: usize = DenseBitSet::FULL.rank(64);
+
+        assert_eq!(MASK.to_integer(), 0xff);
+        assert_eq!(LOW, 0xf);
+        assert_eq!(WEIGHT, 64);
+        assert!(DenseBitSet::EMPTY.none());
+        assert!(DenseBitSet::FULL.all());
+        assert_eq!(DenseBitSet::EMPTY, !DenseBitSet::FULL);
+        assert_eq!(DenseBitSet::EMPTY, DenseBitSet::new());
+    }
+
+    #[test]
+    fn test_equality_non_canonical_dbse() {
+        // `subset` keeps the bits beyond the requested length in the storage
+        let dirty = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(u64::MAX))
+            .subset(0, 12);
+        let clean = DenseBitSetExtended::from_string(String::from("fff"), 16);
+        assert_eq!(dirty, clean);
+
+        let mut h1 = std::collections::hash_map::DefaultHasher::new();
+        let mut h2 = std::collections::hash_map::DefaultHasher::new();
+        dirty.hash(&mut h1);
+        clean.hash(&mut h2);
+        assert_eq!(h1.finish(), h2.finish());
+
+        // Equal sizes but different storage lengths
+        let mut bs1 = DenseBitSetExtended::new();
+        bs1.set_bit(200, false);
+        let mut bs2 = DenseBitSetExtended::new();
+        bs2.grow_words(3);
+        bs2.set_bit(200, false);
+        assert_eq!(bs1, bs2);
+    }
+
+    #[test]
+    fn test_bitops_canonical_dbse() {
+        let dirty = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(u64::MAX))
+            .subset(0, 12);
+        let mut wide = DenseBitSetExtended::new();
+        wide.set_bit(3, true);
+        wide.set_bit(40, true);
+        wide.set_bit(100, true);
+
+        let and = dirty.clone() & wide.clone();
+        assert_eq!(and.get_size(), 12);
+        assert_eq!(and.get_word(0), 0b1000);
+        assert_eq!(
+            and,
+            DenseBitSetExtended::from_string(String::from("008"), 16)
+        );
+
+        let or = dirty.clone() | wide.clone();
+        assert_eq!(or.get_size(), 101);
+        assert_eq!(or.get_word(0), 0xfff | (1 << 40));
+        assert_eq!(or.get_weight(), 14);
+
+        let xor = dirty ^ wide;
+        assert_eq!(xor.get_size(), 101);
+        assert_eq!(xor.get_word(0), 0xff7 | (1 << 40));
+        assert_eq!(xor.get_weight(), 13);
+    }
+
+    #[test]
+    fn test_gather_dbs() {
+        let bs = DenseBitSet::from_integer(0b10001000);
+        let g = bs.gather(&[7, 0, 3]);
+        assert!(g.get_bit(0));
+        assert!(!g.get_bit(1));
+        assert!(g.get_bit(2));
+        assert_eq!(g.to_integer(), 0b101);
+
+        assert_eq!(bs.gather(&[3, 3, 3]).to_integer(), 0b111);
+        assert!(bs.gather(&[]).none());
+        let identity: Vec<usize> = (0..64).collect();
+        assert_eq!(bs.gather(&identity), bs);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_gather_overflow_dbs() {
+        let bs = DenseBitSet::from_integer(1234567890);
+        let _r = bs.gather(&[1, 64]); // Should panic: bit #64 is out of bounds
+    }
+
+    #[test]
+    fn test_write_read_dbse() {
+        let bs = DenseBitSetExtended::from_string(
+            String::from("f001eddadf411eddec0de5ca1ab1ec0feefeeb1e01dc0b01"),
+            16,
+        ) << 7;
+        let mut buffer = vec![];
+        bs.write_to(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), 8 + 4 * 8);
+
+        let mut cursor = std::io::Cursor::new(buffer);
+        let read = DenseBitSetExtended::read_from(&mut cursor).unwrap();
+        assert_eq!(read, bs);
+        assert_eq!(read.get_size(), 199);
+
+        let mut empty = vec![];
+        DenseBitSetExtended::new().write_to(&mut empty).unwrap();
+        let read = DenseBitSetExtended::read_from(&mut empty.as_slice()).unwrap();
+        assert!(read.none());
+        assert_eq!(read.get_size(), 0);
+    }
+
+    #[test]
+    fn catch_read_truncated_dbse() {
+        let bs = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(1234567890));
+        let mut buffer = vec![];
+        bs.write_to(&mut buffer).unwrap();
+        buffer.truncate(12);
+
+        assert!(DenseBitSetExtended::read_from(&mut buffer.as_slice()).is_err());
+    }
+
+    #[test]
+    fn catch_read_huge_size_dbse() {
+        for &size in &[u64::MAX, u64::MAX - 62, 1 << 60] {
+            let mut buffer = size.to_le_bytes().to_vec();
+            buffer.extend_from_slice(&[0xff; 16]);
+
+            let err = DenseBitSetExtended::read_from(&mut buffer.as_slice()).unwrap_err();
+            if size > usize::MAX as u64 - 63 {
+                assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+            } else {
+                assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_masks_top_word_dbse() {
+        let mut buffer = 68u64.to_le_bytes().to_vec();
+        buffer.extend_from_slice(&u64::MAX.to_le_bytes());
+        buffer.extend_from_slice(&u64::MAX.to_le_bytes());
+
+        let mut read = DenseBitSetExtended::read_from(&mut buffer.as_slice()).unwrap();
+        assert_eq!(read.get_size(), 68);
+        assert_eq!(read.get_word(0), u64::MAX);
+        assert_eq!(read.get_word(1), 0b1111);
+        assert_eq!(read.get_weight(), 68);
+
+        read.set_bit(100, false);
+        assert_eq!(read.get_weight(), 68);
+    }
+
+    #[test]
+    fn test_pairwise_hamming_dbs() {
+        let sets = [
+            DenseBitSet::from_integer(0),
+            DenseBitSet::from_integer(0b1011),
+            DenseBitSet::from_integer(0b0110),
+            DenseBitSet::from_integer(u64::MAX),
+        ];
+        let distances = DenseBitSet::pairwise_hamming(&sets);
+
+        assert_eq!(
+            distances,
+            vec![
+                vec![0, 3, 2, 64],
+                vec![3, 0, 3, 61],
+                vec![2, 3, 0, 62],
+                vec![64, 61, 62, 0],
+            ]
+        );
+        assert!(DenseBitSet::pairwise_hamming(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_majority_dbse() {
+        let a = DenseBitSetExtended::from_string(String::from("f0f0"), 16);
+        let b = DenseBitSetExtended::from_string(String::from("ff00"), 16);
+        let c = DenseBitSetExtended::from_string(String::from("0ff0"), 16);
+        let m = DenseBitSetExtended::majority(&a, &b, &c);
+        assert_eq!(
+            m,
+            DenseBitSetExtended::from_string(String::from("fff0"), 16)
+        );
+
+        // Differing sizes: missing bits count as zeros
+        let mut d = DenseBitSetExtended::new();
+        d.set_bit(4, true);
+        d.set_bit(100, true);
+        let mut e = DenseBitSetExtended::new();
+        e.set_bit(100, true);
+        let m = DenseBitSetExtended::majority(&a, &d, &e);
+        assert_eq!(m.get_size(), 101);
+        assert_eq!(m.get_weight(), 2);
+        assert!(m.get_bit(4));
+        assert!(m.get_bit(100));
+    }
+
+    #[test]
+    fn test_conditional_select_dbs() {
+        let a = DenseBitSet::from_integer(1234567890);
+        let b = DenseBitSet::from_integer(0xF0F0F0F0F0F0F0F0);
+
+        assert_eq!(DenseBitSet::conditional_select(true, a, b), a);
+        assert_eq!(DenseBitSet::conditional_select(false, a, b), b);
+        assert_eq!(
+            DenseBitSet::conditional_select(true, DenseBitSet::FULL, DenseBitSet::EMPTY),
+            DenseBitSet::FULL
+        );
+        assert_eq!(
+            DenseBitSet::conditional_select(false, DenseBitSet::FULL, DenseBitSet::EMPTY),
+            DenseBitSet::EMPTY
+        );
+    }
+
+    #[test]
+    fn test_fold_xor_dbse() {
+        let bs1 = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(1234567890));
+        assert_eq!(bs1.fold_xor(), 1234567890);
+
+        let bs2 = DenseBitSetExtended::from_string(String::from("ff00000000000000f0"), 16);
+        let bs3 = DenseBitSetExtended::from_string(String::from("ff00000000000000f0"), 16);
+        assert_eq!(bs2.fold_xor(), bs3.fold_xor());
+        assert_eq!(bs2.fold_xor(), 0xff ^ 0xf0);
+
+        // Bits beyond the size are ignored
+        let dirty = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(u64::MAX))
+            .subset(0, 8);
+        assert_eq!(dirty.fold_xor(), 0xff);
+        assert_eq!(DenseBitSetExtended::new().fold_xor(), 0);
+    }
+
+    #[test]
+    fn test_clock_lfsr_dbs() {
+        // 4-bit maximal-length LFSR held in the top nibble: s(t) = s(t-3) ^ s(t-4)
+        let taps = DenseBitSet::from_integer(0b11 << 60);
+        let mut lfsr = DenseBitSet::from_integer(1 << 60);
+        let mut states = std::collections::HashSet::new();
+
+        for _ in 0..15 {
+            assert!(states.insert(lfsr.extract(60, 4)));
+            lfsr.clock_lfsr(taps);
+        }
+        assert_eq!(lfsr.extract(60, 4), 0b0001);
+        assert!(!states.contains(&0));
+
+        // The output bits are the register bits, from the lowest to the highest
+        let mut lfsr = DenseBitSet::from_integer(0b101);
+        let outputs: Vec<bool> = (0..4)
+            .map(|_| lfsr.clock_lfsr(DenseBitSet::new()))
+            .collect();
+        assert_eq!(outputs, vec![true, false, true, false]);
+        assert!(lfsr.none());
+    }
+
+    #[test]
+    fn test_density_dbse() {
+        assert_eq!(DenseBitSetExtended::new().density(), 0.0);
+
+        let full = DenseBitSetExtended::from_string(String::from("fffffffffffffffffffff"), 16);
+        assert_eq!(full.density(), 1.0);
+
+        let half = DenseBitSetExtended::from_string(String::from("aaaaaaaaaaaaaaaaaaaaaaaa"), 16);
+        assert_eq!(half.density(), 0.5);
+
+        // Bits beyond the size are not counted
+        let dirty = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(u64::MAX))
+            .subset(0, 10);
+        assert_eq!(dirty.get_weight(), 10);
+        assert_eq!(dirty.density(), 1.0);
+    }
+
+    #[test]
+    fn test_bytes_dbs() {
+        let v = 0x0123456789abcdef;
+        let bs = DenseBitSet::from_integer(v);
+        for (i, &b) in bs.bytes().iter().enumerate() {
+            assert_eq!(b, (v >> (8 * i)) as u8);
+            assert_eq!(bs.byte(i), (v >> (8 * i)) as u8);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_byte_overflow_dbs() {
+        let bs = DenseBitSet::from_integer(1234567890);
+        let _r = bs.byte(8); // Should panic: byte #8 is out of bounds
+    }
+
+    #[test]
+    fn test_first_difference_dbse() {
+        let bs1 = DenseBitSetExtended::from_string(
+            String::from("f001eddadf411eddec0de5ca1ab1ec0feefeeb1e01dc0b01"),
+            16,
+        );
+        let mut bs2 = bs1.clone();
+        assert_eq!(bs1.first_difference(&bs2), None);
+
+        bs2.set_bit(170, !bs1.get_bit(170));
+        assert_eq!(bs1.first_difference(&bs2), Some(170));
+        assert_eq!(bs2.first_difference(&bs1), Some(170));
+
+        bs2.set_bit(3, !bs1.get_bit(3));
+        assert_eq!(bs1.first_difference(&bs2), Some(3));
+
+        let mut bs3 = bs1.clone();
+        bs3.set_bit(1000, true);
+        assert_eq!(bs1.first_difference(&bs3), Some(1000));
+    }
+
+    #[test]
+    fn test_saturating_shl_dbs() {
+        let bs = DenseBitSet::from_integer(0b10101);
+        assert_eq!(bs.saturating_shl(6), bs << 6);
+        assert_eq!(bs.saturating_shl(0), bs);
+        assert_eq!(bs.saturating_shl(59), bs << 59);
+        assert_eq!(bs.saturating_shl(60).to_integer(), 0b101 << 60 | 1 << 63);
+        assert_eq!(bs.saturating_shl(62).to_integer(), 0b11 << 62);
+        assert_eq!(bs.saturating_shl(200).to_integer(), 1 << 63);
+
+        // Once reached, the top bit stays set
+        let mut top = DenseBitSet::from_integer(1);
+        for _ in 0..100 {
+            top = top.saturating_shl(1);
+        }
+        assert_eq!(top.to_integer(), 1 << 63);
+        assert!(DenseBitSet::new().saturating_shl(70).none());
+    }
+
+    #[test]
+    fn test_fit_size_dbse() {
+        let mut bs =
+            DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b1011)) << 130;
+        bs.grow_words(10);
+        assert_eq!(bs.get_size(), 640);
+
+        bs.fit_size();
+        assert_eq!(bs.get_size(), 134);
+        assert_eq!(bs.get_weight(), 3);
+        assert!(bs.get_bit(130));
+        assert!(bs.get_bit(131));
+        assert!(bs.get_bit(133));
+        assert_eq!(bs.extract_u64(128, 6), 0b101100);
+
+        let mut empty = DenseBitSetExtended::new();
+        empty.set_bit(300, false);
+        empty.fit_size();
+        assert_eq!(empty.get_size(), 0);
+    }
+
+    #[test]
+    fn test_count_transitions_dbs() {
+        assert_eq!(DenseBitSet::from_integer(0b1100).count_transitions(4), 1);
+        assert_eq!(DenseBitSet::from_integer(0b1010).count_transitions(4), 3);
+        assert_eq!(DenseBitSet::from_integer(0b1100).count_transitions(64), 2);
+        assert_eq!(DenseBitSet::from_integer(0b1100).count_transitions(1), 0);
+        assert_eq!(DenseBitSet::from_integer(u64::MAX).count_transitions(64), 0);
+        assert_eq!(
+            DenseBitSet::from_integer(0x5555555555555555).count_transitions(64),
+            63
+        );
+    }
+
+    #[test]
+    fn test_first_n_set_dbse() {
+        let bs = DenseBitSetExtended::from_string(
+            String::from("f001eddadf411eddec0de5ca1ab1ec0feefeeb1e01dc0b01"),
+            16,
+        );
+        let positions = bs.to_sorted_positions();
+
+        for &n in &[1, 5, 40, 70] {
+            let first = bs.first_n_set(n);
+            assert_eq!(first.get_size(), bs.get_size());
+            assert_eq!(first.to_sorted_positions(), positions[..n].to_vec());
+        }
+        assert!(bs.first_n_set(0).none());
+        assert_eq!(bs.first_n_set(0).get_size(), bs.get_size());
+        assert_eq!(bs.first_n_set(bs.get_weight() as usize), bs);
+        assert_eq!(bs.first_n_set(1000), bs);
+    }
+
+    #[test]
+    fn test_byte_parities_dbs() {
+        for &v in &[
+            0,
+            1234567890,
+            0x0123456789abcdef,
+            u64::MAX,
+            0x8000000000000001,
+        ] {
+            let bs = DenseBitSet::from_integer(v);
+            let mut expected = 0;
+            for i in 0..8 {
+                expected |= ((((v >> (8 * i)) as u8).count_ones() & 1) as u8) << i;
+            }
+            assert_eq!(bs.byte_parities(), expected);
+        }
+    }
+
+    #[test]
+    fn test_toggle_range_dbse() {
+        let mut bs = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0xff00));
+        bs.toggle_range(4, 8);
+        assert_eq!(bs.get_word(0), 0xf0f0);
+        assert_eq!(bs.get_size(), 64);
+
+        // Extending beyond the size sets the new bits
+        bs.toggle_range(60, 140);
+        assert_eq!(bs.get_size(), 200);
+        assert_eq!(bs.get_weight(), 8 + 4 + 136);
+        assert!((64..200).all(|i| bs.get_bit(i)));
+        assert_eq!(bs.extract_u64(56, 8), 0xf0);
+
+        bs.toggle_range(0, 200);
+        assert_eq!(bs.get_weight(), 200 - 148);
+        assert_eq!(bs.get_word(0), !0xf0f0 & 0x0fffffffffffffff);
+
+        // Bits beyond the size of a non-canonical bitset are treated as zeros
+        let mut dirty = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(u64::MAX))
+            .subset(0, 4);
+        dirty.toggle_range(8, 4);
+        assert_eq!(dirty.get_size(), 12);
+        assert_eq!(dirty.get_word(0), 0xf0f);
+
+        // Setting a zero extends the size but not the storage
+        let mut sparse = DenseBitSetExtended::new();
+        sparse.set_bit(300, false);
+        sparse.toggle_range(100, 150);
+        assert_eq!(sparse.get_size(), 301);
+        assert_eq!(sparse.get_weight(), 150);
+        assert!((100..250).all(|i| sparse.get_bit(i)));
+
+        // Growing up to the size limit is allowed
+        sparse.toggle_range(63990, 10);
+        assert_eq!(sparse.get_size(), 64000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_toggle_range_overflow_dbse() {
+        let mut bs = DenseBitSetExtended::new();
+        bs.toggle_range(10, usize::MAX); // Should panic: the end of the range overflows
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_toggle_range_too_large_dbse() {
+        let mut bs = DenseBitSetExtended::new();
+        bs.toggle_range(63990, 20); // Should panic: the bitset would exceed the size limit
+    }
+
+    #[test]
+    fn test_f64_dbs() {
+        for &f in &[0.0, -0.0, 1.5, -1234.5678, f64::MIN_POSITIVE, f64::MAX] {
+            let bs = DenseBitSet::from_f64(f);
+            assert_eq!(bs.to_integer(), f.to_bits());
+            assert_eq!(bs.to_f64().to_bits(), f.to_bits());
+        }
+
+        let inf = DenseBitSet::from_f64(f64::INFINITY);
+        assert_eq!(inf.to_integer(), 0x7ff0000000000000);
+        assert_eq!(inf.to_f64(), f64::INFINITY);
+        assert_eq!(
+            DenseBitSet::from_integer(0xfff0000000000000).to_f64(),
+            f64::NEG_INFINITY
+        );
+
+        let nan = DenseBitSet::from_f64(f64::NAN);
+        assert!(nan.to_f64().is_nan());
+        assert_eq!(nan.extract(52, 11), 0x7ff);
+        assert!(nan.extract(0, 52) != 0);
+    }
+
+    #[test]
+    fn test_mask_with_dbse() {
+        let mut bs = DenseBitSetExtended::new();
+        bs.toggle_range(0, 300);
+        let mut mask = DenseBitSetExtended::new();
+        mask.toggle_range(50, 100);
+
+        bs.mask_with(&mask);
+        bs.mask_with(&mask);
+        assert_eq!(bs.get_size(), 300);
+        assert_eq!(bs.get_weight(), 100);
+        assert_eq!(bs.to_sorted_positions(), (50..150).collect::<Vec<u32>>());
+        assert_eq!(mask.get_weight(), 100);
+    }
+
+    #[test]
+    fn test_nibbles_dbs() {
+        let bs = DenseBitSet::from_integer(0x0123456789abcdef);
+        let nibbles = bs.nibbles();
+        assert_eq!(nibbles[0], bs.extract(0, 4) as u8);
+        assert_eq!(nibbles[0], 0xf);
+        assert_eq!(nibbles[15], 0x0);
+        assert_eq!(nibbles[9], bs.extract(36, 4) as u8);
+        assert_eq!(DenseBitSet::from_nibbles(nibbles), bs);
+
+        // Only the low 4 bits of each value are used
+        assert_eq!(DenseBitSet::from_nibbles([0xff; 16]), DenseBitSet::FULL);
+    }
+
+    #[test]
+    fn test_reverse_word_order_dbse() {
+        let mut bs = DenseBitSetExtended::new();
+        bs.set_word(0, 0x0123456789abcdef);
+        bs.set_word(1, 0);
+        bs.set_word(2, 0b1011);
+        let mut rs = bs.clone();
+        rs.reverse_word_order();
+
+        assert_eq!(rs.get_size(), 192);
+        assert_eq!(rs.get_word(0), 0b1011);
+        assert_eq!(rs.get_word(1), 0);
+        assert_eq!(rs.get_word(2), 0x0123456789abcdef);
+        rs.reverse_word_order();
+        assert_eq!(rs, bs);
+
+        // A partial last word is padded with zeros
+        let mut partial = DenseBitSetExtended::from_string(String::from("1ffffffffffffffff"), 16);
+        assert_eq!(partial.get_size(), 68);
+        partial.reverse_word_order();
+        assert_eq!(partial.get_size(), 128);
+        assert_eq!(partial.get_word(0), 1);
+        assert_eq!(partial.get_word(1), u64::MAX);
+    }
+
+    #[test]
+    fn test_leading_zeros_in_dbs() {
+        let bs = DenseBitSet::from_integer(0b0010);
+        assert_eq!(bs.leading_zeros_in(4), 2);
+        assert_eq!(bs.leading_zeros_in(2), 0);
+        assert_eq!(bs.leading_zeros_in(1), 1);
+        assert_eq!(bs.leading_zeros_in(64), 62);
+
+        let high = DenseBitSet::from_integer(0xf0 | (1 << 63));
+        assert_eq!(high.leading_zeros_in(8), 0);
+        assert_eq!(high.leading_zeros_in(12), 4);
+        assert_eq!(high.leading_zeros_in(64), 0);
+        assert_eq!(DenseBitSet::new().leading_zeros_in(10), 10);
+    }
+
+    #[test]
+    fn test_trailing_zeros_in_dbs() {
+        let bs = DenseBitSet::from_integer(0b101000);
+        assert_eq!(bs.trailing_zeros_in(8), 3);
+        assert_eq!(bs.trailing_zeros_in(3), 3);
+        assert_eq!(bs.trailing_zeros_in(64), 3);
+        assert_eq!(DenseBitSet::from_integer(1).trailing_zeros_in(4), 0);
+        assert_eq!(DenseBitSet::from_integer(1 << 63).trailing_zeros_in(64), 63);
+        assert_eq!(DenseBitSet::new().trailing_zeros_in(64), 64);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_leading_zeros_in_overflow_dbs() {
+        let bs = DenseBitSet::from_integer(1234567890);
+        let _r = bs.leading_zeros_in(65); // Should panic: 65 exceeds the 64 bit boundary
+    }
+
+    #[test]
+    fn test_to_grouped_string_dbse() {
+        let bs = DenseBitSetExtended::from_string(String::from("10101100"), 2);
+        assert_eq!(bs.to_grouped_string(4, ' '), "1010 1100");
+        assert_eq!(bs.to_grouped_string(3, '_'), "10_101_100");
+        assert_eq!(bs.to_grouped_string(8, ' '), "10101100");
+
+        let bs2 = DenseBitSetExtended::from_string(String::from("f0f0f0f0f0f0f0f0f"), 16);
+        let s = bs2.to_grouped_string(8, ' ');
+        assert_eq!(s.len(), 68 + 8);
+        assert!(s.starts_with("1111 00001111 "));
+        assert_eq!(DenseBitSetExtended::new().to_grouped_string(4, ' '), "");
+    }
+
+    #[test]
+    fn test_mask_width_dbs() {
+        assert!(DenseBitSet::from_integer(0b1111).is_low_mask());
+        assert_eq!(DenseBitSet::from_integer(0b1111).mask_width(), Some(4));
+        assert!(!DenseBitSet::from_integer(0b1011).is_low_mask());
+        assert_eq!(DenseBitSet::from_integer(0b1011).mask_width(), None);
+        assert_eq!(DenseBitSet::from_integer(0b1110).mask_width(), None);
+        assert!(DenseBitSet::new().is_low_mask());
+        assert_eq!(DenseBitSet::new().mask_width(), Some(0));
+        assert_eq!(DenseBitSet::FULL.mask_width(), Some(64));
+        assert_eq!(DenseBitSet::from_integer(1 << 63).mask_width(), None);
+    }
+
+    #[test]
+    fn test_changed_positions_dbse() {
+        let mut bs1 = DenseBitSetExtended::new();
+        let mut bs2 = DenseBitSetExtended::new();
+        for &i in &[1, 64, 65, 200] {
+            bs1.set_bit(i, true);
+        }
+        for &i in &[1, 2, 65, 130, 300] {
+            bs2.set_bit(i, true);
+        }
+
+        let changes: Vec<(usize, Side)> = bs1.changed_positions(&bs2).collect();
+        assert_eq!(
+            changes,
+            vec![
+                (2, Side::Right),
+                (64, Side::Left),
+                (130, Side::Right),
+                (200, Side::Left),
+                (300, Side::Right),
+            ]
+        );
+        let reversed: Vec<(usize, Side)> = bs2.changed_positions(&bs1).collect();
+        assert_eq!(reversed[0], (2, Side::Left));
+        assert_eq!(bs1.changed_positions(&bs1.clone()).count(), 0);
+    }
+
+    #[test]
+    fn test_ilog2_dbs() {
+        for i in 0..64 {
+            let bs = DenseBitSet::from_integer(1 << i);
+            assert_eq!(bs.ilog2_floor(), Some(i));
+            assert_eq!(bs.ilog2_ceil(), Some(i));
+        }
+        let bs = DenseBitSet::from_integer(0b1011);
+        assert_eq!(bs.ilog2_floor(), Some(3));
+        assert_eq!(bs.ilog2_ceil(), Some(4));
+        assert_eq!(DenseBitSet::FULL.ilog2_floor(), Some(63));
+        assert_eq!(DenseBitSet::FULL.ilog2_ceil(), Some(64));
+        assert_eq!(DenseBitSet::new().ilog2_floor(), None);
+        assert_eq!(DenseBitSet::new().ilog2_ceil(), None);
+    }
+
+    #[test]
+    fn test_common_prefix_len_dbse() {
+        let bs = DenseBitSetExtended::from_string(
+            String::from("f001eddadf411eddec0de5ca1ab1ec0feefeeb1e01dc0b01"),
+            16,
+        );
+        for &p in &[0, 1, 63, 64, 100, 191] {
+            let mut other = bs.clone();
+            other.set_bit(p, !bs.get_bit(p));
+            assert_eq!(bs.common_prefix_len(&other), p);
+        }
+        assert_eq!(bs.common_prefix_len(&bs.clone()), 192);
+
+        let short = bs.subset(0, 70);
+        assert_eq!(bs.common_prefix_len(&short), 70);
+        assert_eq!(short.common_prefix_len(&bs), 70);
+    }
+
+    #[test]
+    fn test_swap_lanes_dbs() {
+        let v: u64 = 0x0123456789abcdef;
+        let bs = DenseBitSet::from_integer(v);
+
+        assert_eq!(bs.swap_halves().to_integer(), 0x89abcdef01234567);
+        assert_eq!(bs.swap_halves().swap_halves(), bs);
+
+        let mut u16_lanes = 0;
+        let mut u8_lanes = 0;
+        for i in 0..4 {
+            let lane = (v >> (16 * i)) & 0xffff;
+            u16_lanes |= lane << (16 * (i ^ 1));
+            let lane = (lane >> 8) | ((lane & 0xff) << 8);
+            u8_lanes |= lane << (16 * i);
+        }
+        assert_eq!(bs.swap_u16_lanes().to_integer(), u16_lanes);
+        assert_eq!(bs.swap_u8_lanes().to_integer(), u8_lanes);
+        assert_eq!(
+            bs.swap_halves()
+                .swap_u16_lanes()
+                .swap_u8_lanes()
+                .to_integer(),
+            v.swap_bytes()
+        );
+    }
+
+    #[test]
+    fn test_try_resize_value_dbse() {
+        let mut bs =
+            DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b1011)) << 100;
+        assert_eq!(bs.get_size(), 164);
+
+        // Safe shrink
+        assert!(bs.try_resize_value(104).is_ok());
+        assert_eq!(bs.get_size(), 104);
+        assert_eq!(bs.to_sorted_positions(), vec![100, 101, 103]);
+
+        // Lossy shrink
+        assert!(bs.try_resize_value(103).is_err());
+        assert!(bs.try_resize_value(50).is_err());
+        assert_eq!(bs.get_size(), 104);
+        assert_eq!(bs.get_weight(), 3);
+
+        // Grow
+        assert!(bs.try_resize_value(300).is_ok());
+        assert_eq!(bs.get_size(), 300);
+        assert_eq!(bs.to_sorted_positions(), vec![100, 101, 103]);
+
+        let mut empty = DenseBitSetExtended::new();
+        empty.set_bit(80, false);
+        assert!(empty.try_resize_value(0).is_ok());
+        assert_eq!(empty.get_size(), 0);
+    }
+
+    #[test]
+    fn test_isolate_set_dbs() {
+        let bs = DenseBitSet::from_integer(1234567890);
+        assert_eq!(bs.isolate_lowest_set().to_integer(), 2);
+        assert_eq!(bs.isolate_nth_set(0), bs.isolate_lowest_set());
+        assert_eq!(bs.isolate_nth_set(5).to_integer(), 1 << 17);
+        assert_eq!(bs.isolate_nth_set(11).to_integer(), 1 << 30);
+        assert!(bs.isolate_nth_set(12).none());
+        assert!(DenseBitSet::new().isolate_lowest_set().none());
+        assert_eq!(
+            DenseBitSet::from_integer(1 << 63)
+                .isolate_lowest_set()
+                .to_integer(),
+            1 << 63
+        );
+    }
+
+    #[test]
+    fn test_stable_hash64_dbse() {
+        assert_eq!(
+            DenseBitSetExtended::new().stable_hash64(),
+            0xa8c7f832281a39c5
+        );
+
+        let bs = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(1234567890));
+        assert_eq!(bs.stable_hash64(), 0xd05de21452af86b0);
+
+        let mut bs2 = DenseBitSetExtended::new();
+        bs2.insert_u64(0xffff, 0, 16);
+        bs2.set_bit(99, true);
+        assert_eq!(bs2.get_size(), 100);
+        assert_eq!(bs2.stable_hash64(), 0x362d340025dc82f);
+
+        // Bits beyond the size do not affect the hash
+        let dirty = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(u64::MAX))
+            .subset(0, 12);
+        let clean = DenseBitSetExtended::from_string(String::from("fff"), 16);
+        assert_eq!(dirty.stable_hash64(), clean.stable_hash64());
+    }
+
+    #[test]
+    fn test_numeric_eq_dbse() {
+        let bs1 = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(1234567890));
+        let mut bs2 = bs1.clone();
+        bs2.set_bit(500, false);
+        assert_ne!(bs1.get_size(), bs2.get_size());
+        assert!(bs1.numeric_eq(&bs2));
+        assert!(bs2.numeric_eq(&bs1));
+
+        bs2.set_bit(400, true);
+        assert!(!bs1.numeric_eq(&bs2));
+        assert!(DenseBitSetExtended::new().numeric_eq(&DenseBitSetExtended::with_capacity(100)));
+    }
+
+    #[test]
+    fn test_split_by_weight_dbse() {
+        let bs = DenseBitSetExtended::from_string(
+            String::from("f001eddadf411eddec0de5ca1ab1ec0feefeeb1e01dc0b01"),
+            16,
+        );
+        let weight = bs.get_weight();
+
+        for &n in &[1, 3, 7, 200] {
+            let parts = bs.split_by_weight(n);
+            assert_eq!(parts.len(), n);
+
+            let mut last = 0;
+            for p in &parts {
+                let w = p.get_weight();
+                assert!(w == weight / n as u32 || w == weight / n as u32 + 1);
+                assert_eq!(p.get_size(), bs.get_size());
+
+                // Parts hold contiguous ranges of positions
+                let positions = p.to_sorted_positions();
+                if let Some(&first) = positions.first() {
+                    assert!(first >= last);
+                    last = *positions.last().unwrap();
+                }
+            }
+            let union = parts
+                .iter()
+                .fold(DenseBitSetExtended::new(), |acc, p| acc | p.clone());
+            assert_eq!(union.get_weight(), weight);
+            assert!(union.numeric_eq(&bs));
+        }
+    }
+
+    #[test]
+    fn test_byte_popcounts_dbs() {
+        for &v in &[
+            0,
+            1234567890,
+            0x0123456789abcdef,
+            u64::MAX,
+            0x8000000000000001,
+        ] {
+            let bs = DenseBitSet::from_integer(v);
+            let counts = bs.byte_popcounts();
+            for (i, &c) in counts.iter().enumerate() {
+                assert_eq!(u32::from(c), ((v >> (8 * i)) as u8).count_ones());
+            }
+            let total: u32 = counts.iter().map(|&c| u32::from(c)).sum();
+            assert_eq!(total, bs.get_weight());
+        }
+    }
+
+    #[test]
+    fn test_iter_ones_chunked_dbse() {
+        let bs = DenseBitSetExtended::from_string(
+            String::from("f001eddadf411eddec0de5ca1ab1ec0feefeeb1e01dc0b01"),
+            16,
+        );
+        let expected: Vec<usize> = bs
+            .to_sorted_positions()
+            .iter()
+            .map(|&p| p as usize)
+            .collect();
+
+        for &batch in &[1, 7, 64, 1000] {
+            let batches: Vec<Vec<usize>> = bs.iter_ones_chunked(batch).collect();
+            assert!(batches.iter().all(|b| !b.is_empty() && b.len() <= batch));
+            assert_eq!(batches.concat(), expected);
+        }
+        assert_eq!(DenseBitSetExtended::new().iter_ones_chunked(4).count(), 0);
+    }
+
+    #[test]
+    fn test_bit_reverse_low_dbs() {
+        assert_eq!(
+            DenseBitSet::from_integer(0b001).bit_reverse_low(3),
+            DenseBitSet::from_integer(0b100)
+        );
+        assert_eq!(
+            DenseBitSet::from_integer(0b110).bit_reverse_low(3),
+            DenseBitSet::from_integer(0b011)
+        );
+        assert_eq!(
+            DenseBitSet::from_integer(0b1111_0110).bit_reverse_low(4),
+            DenseBitSet::from_integer(0b0110)
+        );
+
+        let bs = DenseBitSet::from_integer(1234567890);
+        assert_eq!(bs.bit_reverse_low(64), bs.reverse());
+        assert!(bs.bit_reverse_low(0).none());
+
+        // Bit-reversal permutation of 3-bit indices
+        let perm: Vec<u64> = (0..8)
+            .map(|i| DenseBitSet::from_integer(i).bit_reverse_low(3).to_integer())
+            .collect();
+        assert_eq!(perm, vec![0, 4, 2, 6, 1, 5, 3, 7]);
+    }
+
+    #[test]
+    fn test_push_pop_bit_dbse() {
+        let bits: Vec<bool> = (0..150).map(|i| i % 3 == 0 || i % 7 == 0).collect();
+        let mut bs = DenseBitSetExtended::new();
+        for &b in &bits {
+            bs.push_bit(b);
+        }
+        assert_eq!(bs.get_size(), 150);
+        assert!((0..150).all(|i| bs.get_bit(i) == bits[i]));
+
+        for &b in bits.iter().rev() {
+            assert_eq!(bs.pop_bit(), Some(b));
+        }
+        assert_eq!(bs.get_size(), 0);
+        assert_eq!(bs.pop_bit(), None);
+        assert!(bs.none());
+
+        // Popped bits do not reappear when pushing again
+        bs.push_bit(true);
+        bs.push_bit(true);
+        bs.pop_bit();
+        bs.push_bit(false);
+        assert_eq!(bs.get_weight(), 1);
+    }
+
+    #[test]
+    fn test_parse_auto_dbs() {
+        assert_eq!(
+            DenseBitSet::parse_auto("0b101110001").unwrap().to_integer(),
+            0b101110001
+        );
+        assert_eq!(
+            DenseBitSet::parse_auto("0o755").unwrap().to_integer(),
+            0o755
+        );
+        assert_eq!(
+            DenseBitSet::parse_auto("0xFFFFF").unwrap().to_integer(),
+            0xfffff
+        );
+        assert_eq!(
+            DenseBitSet::parse_auto("123465").unwrap().to_integer(),
+            123465
+        );
+        assert_eq!(DenseBitSet::parse_auto("0").unwrap().to_integer(), 0);
+        assert_eq!(
+            DenseBitSet::parse_auto("0xffffffffffffffff").unwrap(),
+            DenseBitSet::FULL
+        );
+    }
+
+    #[test]
+    fn catch_parse_auto_invalid_dbs() {
+        assert!(DenseBitSet::parse_auto("Hello World!").is_err());
+        assert!(DenseBitSet::parse_auto("0b102").is_err());
+        assert!(DenseBitSet::parse_auto("0o8").is_err());
+        assert!(DenseBitSet::parse_auto("0x").is_err());
+        assert!(DenseBitSet::parse_auto("").is_err());
+        assert!(DenseBitSet::parse_auto("0x10000000000000000").is_err());
+
+        // Signs are rejected, before or after the prefix
+        for s in &["+5", "-5", "+", "0x+ff", "0b-1", "0o+7"] {
+            assert_eq!(
+                DenseBitSet::parse_auto(s).unwrap_err().kind(),
+                &std::num::IntErrorKind::InvalidDigit
+            );
+        }
+
+        // Prefixes are lowercase only
+        for s in &["0XFF", "0B101", "0O17"] {
+            assert!(DenseBitSet::parse_auto(s).is_err());
+        }
+    }
+
+    #[test]
+    fn test_bitwise_select_dbse() {
+        let mut mask = DenseBitSetExtended::new();
+        let mut ones = DenseBitSetExtended::new();
+        for i in 0..200 {
+            mask.push_bit(i % 2 == 0);
+            ones.push_bit(true);
+        }
+        let mut zeros = DenseBitSetExtended::new();
+        zeros.grow_words(3);
+
+        let s = DenseBitSetExtended::bitwise_select(&mask, &ones, &zeros);
+        assert_eq!(s, mask);
+        let s = DenseBitSetExtended::bitwise_select(&mask, &zeros, &ones);
+        assert_eq!(s.get_size(), 200);
+        assert!((0..200).all(|i| s.get_bit(i) == (i % 2 == 1)));
+    }
+
+    #[test]
+    fn test_wrapping_rot_dbs() {
+        let dbs = DenseBitSet::from_integer(0xdead_beef_0123_4567);
+        for shift in 0..200usize {
+            let mut l = dbs;
+            l.rotl((shift % 64) as u32);
+            let mut r = dbs;
+            r.rotr((shift % 64) as u32);
+            assert_eq!(dbs.wrapping_rotl(shift), l);
+            assert_eq!(dbs.wrapping_rotr(shift), r);
+            assert_eq!(dbs.wrapping_rotl(shift), dbs.wrapping_rotl(shift + 64));
+            assert_eq!(dbs.wrapping_rotl(shift).wrapping_rotr(shift), dbs);
+        }
+        assert_eq!(dbs.wrapping_rotl(64), dbs);
+        assert_eq!(dbs.wrapping_rotr(usize::MAX), dbs.wrapping_rotr(63));
+    }
+
+    #[test]
+    fn test_flip_dbs() {
+        let orig = DenseBitSet::from_integer(0xdead_beef_0123_4567);
+        let mut dbs = orig;
+        dbs.flip();
+        assert_eq!(dbs, !orig);
+        dbs.flip();
+        assert_eq!(dbs, orig);
+    }
+
+    #[test]
+    fn test_flip_dbse() {
+        let orig = DenseBitSetExtended::from_string(String::from("1011001110001111000011111"), 2);
+        assert_eq!(orig.get_size() % 64, 25);
+        let mut dbse = orig.clone();
+        dbse.flip();
+        assert_eq!(dbse.get_size(), orig.get_size());
+        assert_eq!(dbse.get_weight(), 25 - orig.get_weight());
+        assert_eq!(dbse, !orig.clone());
+        dbse.flip();
+        assert_eq!(dbse, orig);
+
+        let mut long = DenseBitSetExtended::with_capacity(130);
+        long.set_bit(129, true);
+        long.set_bit(3, true);
+        let orig = long.clone();
+        long.flip();
+        assert_eq!(long.get_weight(), 128);
+        assert!(!long.get_bit(3) && !long.get_bit(129) && long.get_bit(128));
+        long.flip();
+        assert_eq!(long, orig);
+
+        let mut empty = DenseBitSetExtended::new();
+        empty.flip();
+        assert_eq!(empty.get_weight(), 0);
+    }
+
+    #[test]
+    fn test_to_decimal_string_dbse() {
+        let values: [u128; 6] = [
+            0,
+            1,
+            10_000_000_000_000_000_000,
+            u128::from(u64::MAX) + 1,
+            0x1234_5678_9abc_def0_0fed_cba9_8765_4321,
+            u128::MAX,
+        ];
+        for &v in values.iter() {
+            let lo = DenseBitSet::from_integer(v as u64);
+            let hi = DenseBitSet::from_integer((v >> 64) as u64);
+            let mut dbse = DenseBitSetExtended::from_dense_bitset(hi) << 64;
+            dbse.insert_u64(lo.to_integer(), 0, 64);
+            assert_eq!(dbse.to_decimal_string(), v.to_string());
+        }
+
+        let dbse = DenseBitSetExtended::from_string(String::from("f"), 16);
+        assert_eq!(dbse.get_size(), 4);
+        assert_eq!(dbse.to_decimal_string(), "15");
+
+        // 2^200
+        let mut big = DenseBitSetExtended::with_capacity(201);
+        big.set_bit(200, true);
+        assert_eq!(
+            big.to_decimal_string(),
+            "1606938044258990275541962092341162602522202993782792835301376"
+        );
+    }
+
+    #[test]
+    fn test_iter_ones_dbs() {
+        let dbs = DenseBitSet::from_integer(0b101001);
+        assert_eq!(dbs.iter_ones().collect::<Vec<_>>(), vec![0, 3, 5]);
+        assert_eq!(DenseBitSet::new().iter_ones().next(), None);
+        assert_eq!(DenseBitSet::new().iter_ones().len(), 0);
+
+        let dbs = DenseBitSet::from_integer(0xf000_0000_0000_0001);
+        let mut it = dbs.iter_ones();
+        assert_eq!(it.len(), dbs.get_weight() as usize);
+        assert_eq!(it.next(), Some(0));
+        assert_eq!(it.len(), 4);
+        assert_eq!(it.collect::<Vec<_>>(), vec![60, 61, 62, 63]);
+
+        let all = DenseBitSet::from_integer(u64::MAX);
+        assert_eq!(
+            all.iter_ones().collect::<Vec<_>>(),
+            (0..64).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_matches_dbs() {
+        let dbs = DenseBitSet::from_integer(0xdead_beef);
+        let full = DenseBitSet::from_integer(u64::MAX);
+        assert!(dbs.matches(dbs, full));
+        assert!(!dbs.matches(DenseBitSet::from_integer(0xdead_beee), full));
+        // Nothing to care about: always matches
+        assert!(dbs.matches(DenseBitSet::new(), DenseBitSet::new()));
+        // Only the low nibble matters
+        let low = DenseBitSet::from_integer(0xf);
+        assert!(dbs.matches(DenseBitSet::from_integer(0x1234_000f), low));
+        assert!(!dbs.matches(DenseBitSet::from_integer(0xdead_bee0), low));
+    }
+
+    #[test]
+    fn test_count_matching_dbs() {
+        let sets: Vec<DenseBitSet> = (0..16).map(DenseBitSet::from_integer).collect();
+        let full = DenseBitSet::from_integer(u64::MAX);
+        let pattern = DenseBitSet::from_integer(0b0101);
+        assert_eq!(DenseBitSet::count_matching(&sets, pattern, full), 1);
+        // Bits 0 and 2 set, bits 1 and 3 free
+        let care = DenseBitSet::from_integer(0b0101);
+        assert_eq!(DenseBitSet::count_matching(&sets, pattern, care), 4);
+        assert_eq!(
+            DenseBitSet::count_matching(&sets, pattern, DenseBitSet::new()),
+            16
+        );
+        assert_eq!(DenseBitSet::count_matching(&[], pattern, care), 0);
+    }
+
+    #[test]
+    fn test_iter_ones_dbse() {
+        let mut dbse = DenseBitSetExtended::new();
+        for &p in [3, 70, 1234].iter() {
+            dbse.set_bit(p, true);
+        }
+        assert_eq!(dbse.iter_ones().collect::<Vec<_>>(), vec![3, 70, 1234]);
+
+        assert_eq!(DenseBitSetExtended::new().iter_ones().next(), None);
+        assert_eq!(
+            DenseBitSetExtended::with_capacity(500).iter_ones().next(),
+            None
+        );
+
+        // Bits beyond the size are never yielded
+        let dbse = DenseBitSetExtended::from_string(String::from("f"), 16) << 62;
+        assert_eq!(dbse.iter_ones().collect::<Vec<_>>(), vec![62, 63, 64, 65]);
+        let dbse = DenseBitSetExtended::from_string(String::from("1"), 2);
+        assert_eq!(dbse.iter_ones().collect::<Vec<_>>(), vec![0]);
+
+        let dbse = DenseBitSetExtended::from_sorted_positions(&[0, 63, 64, 127, 128, 200]);
+        assert_eq!(
+            dbse.iter_ones().map(|p| p as u32).collect::<Vec<_>>(),
+            dbse.to_sorted_positions()
+        );
+    }
+
+    #[test]
+    fn test_common_positions_dbse() {
+        let a = DenseBitSetExtended::from_sorted_positions(&[0, 3, 64, 65, 127, 300, 1000]);
+        let b = DenseBitSetExtended::from_sorted_positions(&[3, 4, 65, 127, 128, 1000]);
+        let expected: Vec<usize> = (a.clone() & b.clone()).iter_ones().collect();
+        assert_eq!(expected, vec![3, 65, 127, 1000]);
+        assert_eq!(a.common_positions(&b).collect::<Vec<_>>(), expected);
+        assert_eq!(b.common_positions(&a).collect::<Vec<_>>(), expected);
+
+        // Sizes differ: positions beyond the shorter bitset are never common
+        let short = DenseBitSetExtended::from_sorted_positions(&[0, 3, 64]);
+        assert_eq!(
+            a.common_positions(&short).collect::<Vec<_>>(),
+            vec![0, 3, 64]
+        );
+
+        let empty = DenseBitSetExtended::new();
+        assert_eq!(a.common_positions(&empty).next(), None);
+        assert_eq!(
+            a.common_positions(&a).collect::<Vec<_>>(),
+            a.iter_ones().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_into_iter_dbse() {
+        let dbse = DenseBitSetExtended::from_sorted_positions(&[0, 2, 63, 64, 130]);
+        let bits: Vec<bool> = (&dbse).into_iter().collect();
+        assert_eq!(bits.len(), dbse.get_size());
+        assert_eq!(
+            bits.iter().filter(|&&b| b).count(),
+            dbse.get_weight() as usize
+        );
+        for (i, b) in bits.iter().enumerate() {
+            assert_eq!(*b, [0, 2, 63, 64, 130].contains(&i));
+        }
+
+        let mut count = 0;
+        for b in &dbse {
+            if b {
+                count += 1;
+            }
+        }
+        assert_eq!(count, 5);
+        assert_eq!((&dbse).into_iter().len(), dbse.get_size());
+
+        // Junk beyond the size is not yielded
+        let dbse = DenseBitSetExtended::from_string(String::from("f"), 16);
+        assert_eq!((&dbse).into_iter().collect::<Vec<_>>(), vec![true; 4]);
+        assert_eq!((&DenseBitSetExtended::new()).into_iter().next(), None);
+    }
+
+    #[test]
+    fn test_expand_to_bytes_dbs() {
+        let dbs = DenseBitSet::from_integer(0x8000_0000_dead_beef);
+        let lanes = dbs.expand_to_bytes();
+        for (i, &b) in lanes.iter().enumerate() {
+            assert_eq!(b, if dbs.get_bit(i) { 0xFF } else { 0x00 });
+        }
+        // Collapsing the lanes back gives the original bitset
+        let back = lanes
+            .iter()
+            .enumerate()
+            .fold(0u64, |acc, (i, &b)| acc | (u64::from(b & 1) << i));
+        assert_eq!(back, dbs.to_integer());
+
+        assert_eq!(DenseBitSet::new().expand_to_bytes(), [0; 64]);
+        assert_eq!(
+            DenseBitSet::from_integer(u64::MAX).expand_to_bytes(),
+            [0xFF; 64]
+        );
+    }
+
+    #[test]
+    fn test_from_iter_dbse() {
+        let dbse: DenseBitSetExtended = [3, 70, 1234].iter().cloned().collect();
+        assert_eq!(dbse.get_size(), 1235);
+        assert_eq!(dbse.iter_ones().collect::<Vec<_>>(), vec![3, 70, 1234]);
+
+        let dbse: DenseBitSetExtended = (10..200).collect();
+        assert_eq!(dbse.get_weight(), 190);
+        assert_eq!(dbse.get_size(), 200);
+        assert!(!dbse.get_bit(9) && dbse.get_bit(10) && dbse.get_bit(199));
+
+        // Order and duplicates do not matter
+        let dbse: DenseBitSetExtended = vec![64, 0, 64, 5].into_iter().collect();
+        assert_eq!(
+            dbse,
+            DenseBitSetExtended::from_sorted_positions(&[0, 5, 64])
+        );
+
+        let empty: DenseBitSetExtended = std::iter::empty().collect();
+        assert_eq!(empty.get_size(), 0);
+        assert_eq!(empty.get_weight(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_from_iter_overflow_dbse() {
+        // Should panic: the position exceeds the size limit (and its successor overflows)
+        let _dbse: DenseBitSetExtended = vec![3, usize::MAX].into_iter().collect();
+    }
+
+    #[test]
+    fn test_normalized_dbse() {
+        let mut dbse = DenseBitSetExtended::from_sorted_positions(&[1, 70]);
+        dbse.grow_words(4);
+        let size = dbse.get_size();
+        assert_eq!(size, 256);
+        let (normalized, trimmed) = dbse.normalized();
+        assert_eq!(normalized.get_size(), 71);
+        assert_eq!(trimmed, size - 71);
+        assert_eq!(normalized.iter_ones().collect::<Vec<_>>(), vec![1, 70]);
+        assert!(normalized.numeric_eq(&dbse));
+        assert_eq!(normalized.get_word(2), 0);
+
+        // Already canonical
+        let (again, trimmed) = normalized.normalized();
+        assert_eq!(again, normalized);
+        assert_eq!(trimmed, 0);
+
+        let mut zero = DenseBitSetExtended::new();
+        zero.grow_words(2);
+        let (zero, trimmed) = zero.normalized();
+        assert_eq!(zero.get_size(), 0);
+        assert_eq!(trimmed, 128);
+    }
+
+    #[test]
+    fn test_extend_dbse() {
+        let mut dbse: DenseBitSetExtended = [1, 2, 3].iter().cloned().collect();
+        dbse.extend(vec![5, 9, 400]);
+        assert_eq!(dbse.get_size(), 401);
+        assert_eq!(
+            dbse.iter_ones().collect::<Vec<_>>(),
+            vec![1, 2, 3, 5, 9, 400]
+        );
+
+        // Positions below the current size do not change it
+        dbse.extend(vec![0, 64]);
+        assert_eq!(dbse.get_size(), 401);
+        assert_eq!(dbse.get_weight(), 8);
+
+        dbse.extend(std::iter::empty());
+        assert_eq!(dbse.get_weight(), 8);
+
+        let mut short = DenseBitSetExtended::from_string(String::from("f"), 16);
+        short.extend(vec![10]);
+        assert_eq!(short.iter_ones().collect::<Vec<_>>(), vec![0, 1, 2, 3, 10]);
+
+        // Setting a zero extends the size but not the storage
+        let mut sparse = DenseBitSetExtended::new();
+        sparse.set_bit(300, false);
+        sparse.extend(vec![200]);
+        assert_eq!(sparse.get_size(), 301);
+        assert_eq!(sparse.iter_ones().collect::<Vec<_>>(), vec![200]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_extend_overflow_dbse() {
+        let mut dbse = DenseBitSetExtended::new();
+        dbse.extend(vec![usize::MAX]); // Should panic: the position exceeds the size limit
+    }
+
+    #[test]
+    fn test_merge_dbs() {
+        let reg = DenseBitSet::from_integer(0x1111_2222_3333_4444);
+        // Update the field spanning bits 16..32
+        let field = DenseBitSet::from_integer(0xBEEF << 16);
+        let mask = DenseBitSet::from_integer(0xFFFF << 16);
+        let merged = reg.merge(field, mask);
+        assert_eq!(merged.to_integer(), 0x1111_2222_BEEF_4444);
+        assert_eq!(merged.extract(0, 16), reg.extract(0, 16));
+        assert_eq!(merged.extract(32, 32), reg.extract(32, 32));
+
+        // Bits of `other` outside the mask are ignored
+        let noisy = DenseBitSet::from_integer(0xFFFF_FFFF_BEEF_FFFF);
+        assert_eq!(reg.merge(noisy, mask), merged);
+
+        let full = DenseBitSet::from_integer(u64::MAX);
+        assert_eq!(reg.merge(noisy, full), noisy);
+        assert_eq!(reg.merge(noisy, DenseBitSet::new()), reg);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_dbs() {
+        let dbs = DenseBitSet::from_integer(0xdead_beef_0123_4567);
+        let json = serde_json::to_string(&dbs).unwrap();
+        assert_eq!(json, (0xdead_beef_0123_4567u64).to_string());
+        let back: DenseBitSet = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, dbs);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_dbse() {
+        // A size which is not a multiple of 64
+        let dbse = DenseBitSetExtended::from_sorted_positions(&[0, 5, 64, 99]);
+        assert_eq!(dbse.get_size(), 100);
+        let json = serde_json::to_string(&dbse).unwrap();
+        let back: DenseBitSetExtended = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.get_size(), 100);
+        assert_eq!(back, dbse);
+        assert_eq!(back.iter_ones().collect::<Vec<_>>(), vec![0, 5, 64, 99]);
+
+        // Trailing zeros are part of the size
+        let mut padded = DenseBitSetExtended::from_string(String::from("101"), 2);
+        padded.push_bit(false);
+        let back: DenseBitSetExtended =
+            serde_json::from_str(&serde_json::to_string(&padded).unwrap()).unwrap();
+        assert_eq!(back.get_size(), 4);
+        assert_eq!(back, padded);
+
+        let empty = DenseBitSetExtended::new();
+        let back: DenseBitSetExtended =
+            serde_json::from_str(&serde_json::to_string(&empty).unwrap()).unwrap();
+        assert_eq!(back, empty);
+    }
+
+    #[test]
+    fn test_random_operations_dbse() {
+        // Deterministic xorshift generator
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut rand = move |n: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % n as u64) as usize
+        };
+
+        for _ in 0..200 {
+            // The bitset is checked against a reference model
+            let mut dbse = DenseBitSetExtended::new();
+            let mut model: Vec<bool> = vec![];
+            for _ in 0..30 {
+                match rand(9) {
+                    0 | 1 => {
+                        // Setting a zero beyond the storage only grows the size
+                        let p = rand(300);
+                        let v = rand(2) == 0;
+                        dbse.set_bit(p, v);
+                        if p >= model.len() {
+                            model.resize(p + 1, false);
+                        }
+                        model[p] = v;
+                    }
+                    2 => {
+                        let k = rand(3) * 64 + rand(2) * rand(64);
+                        dbse <<= k;
+                        let mut shifted = vec![false; k];
+                        shifted.extend_from_slice(&model);
+                        model = shifted;
+                    }
+                    3 => {
+                        let k = rand(3) * 64 + rand(2) * rand(64);
+                        dbse >>= k;
+                        model = if k >= model.len() {
+                            vec![]
+                        } else {
+                            model[k..].to_vec()
+                        };
+                    }
+                    4..=6 => {
+                        let positions: Vec<usize> = (0..rand(5)).map(|_| rand(200)).collect();
+                        let mut other = DenseBitSetExtended::new();
+                        let mut other_model = vec![false; rand(200)];
+                        other.set_bit(other_model.len(), false);
+                        other_model.push(false);
+                        for &p in positions.iter() {
+                            other.set_bit(p, true);
+                            if p >= other_model.len() {
+                                other_model.resize(p + 1, false);
+                            }
+                            other_model[p] = true;
+                        }
+                        let op = rand(3);
+                        // The size of the result of & is the smallest size, the largest otherwise
+                        let len = if op == 2 {
+                            min(model.len(), other_model.len())
+                        } else {
+                            max(model.len(), other_model.len())
+                        };
+                        let bit = |m: &Vec<bool>, i: usize| i < m.len() && m[i];
+                        model = (0..len)
+                            .map(|i| match op {
+                                0 => bit(&model, i) | bit(&other_model, i),
+                                1 => bit(&model, i) ^ bit(&other_model, i),
+                                _ => bit(&model, i) & bit(&other_model, i),
+                            })
+                            .collect();
+                        match op {
+                            0 => dbse |= other,
+                            1 => dbse ^= other,
+                            _ => dbse &= other,
+                        }
+                    }
+                    7 => {
+                        dbse = !dbse;
+                        model = model.iter().map(|b| !b).collect();
+                    }
+                    _ => {
+                        dbse.pop_bit();
+                        model.pop();
+                    }
+                }
+
+                assert_eq!(dbse.get_size(), model.len());
+                assert_eq!(
+                    dbse.get_weight() as usize,
+                    model.iter().filter(|&&b| b).count()
+                );
+                assert_eq!(dbse.all(), model.iter().all(|&b| b));
+                assert_eq!(dbse.any(), model.iter().any(|&b| b));
+                for p in 0..model.len() + 70 {
+                    assert_eq!(dbse.get_bit(p), p < model.len() && model[p]);
+                }
+                let position = rand(model.len() + 70);
+                let length = 1 + rand(64);
+                let expected = (0..length)
+                    .filter(|&i| position + i < model.len() && model[position + i])
+                    .fold(0u64, |acc, i| acc | (1 << i));
+                assert_eq!(dbse.extract_u64(position, length), expected);
+                assert_eq!(
+                    dbse.clone().to_string().len(),
+                    max(1, (model.len() + 63) >> 6) * 64
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_le_be_bytes_dbs() {
+        let dbs = DenseBitSet::from_le_bytes([1, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(dbs.get_bit(0));
+        assert_eq!(dbs.get_weight(), 1);
+        let dbs = DenseBitSet::from_be_bytes([1, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(dbs.get_bit(56));
+        assert_eq!(dbs.get_weight(), 1);
+
+        let dbs = DenseBitSet::from_integer(0x0123_4567_89ab_cdef);
+        assert_eq!(DenseBitSet::from_le_bytes(dbs.to_le_bytes()), dbs);
+        assert_eq!(DenseBitSet::from_be_bytes(dbs.to_be_bytes()), dbs);
+        assert_eq!(dbs.to_le_bytes(), dbs.bytes());
+        let mut reversed = dbs.to_le_bytes();
+        reversed.reverse();
+        assert_eq!(dbs.to_be_bytes(), reversed);
+    }
+
+    #[test]
+    fn test_to_bits_dbs() {
+        let dbs = DenseBitSet::from_integer(0xf0a5);
+        let low: [bool; 8] = dbs.to_bits();
+        assert_eq!(low, [true, false, true, false, false, true, false, true]);
+
+        let all: [bool; 64] = dbs.to_bits();
+        for (i, &b) in all.iter().enumerate() {
+            assert_eq!(b, dbs.get_bit(i));
+        }
+        assert_eq!(dbs.to_bits::<0>(), [false; 0]);
+    }
+
+    #[test]
+    fn test_to_bytes_dbse() {
+        let dbse = DenseBitSetExtended::from_sorted_positions(&[0, 9, 64, 70]);
+        assert_eq!(dbse.get_size(), 71);
+        assert_eq!(dbse.to_bytes(), vec![0x01, 0x02, 0, 0, 0, 0, 0, 0, 0x41]);
+
+        // Bits beyond the size are masked in the last byte
+        let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0xffff)) >> 60;
+        assert_eq!(dbse.get_size(), 4);
+        assert_eq!(dbse.to_bytes(), vec![0]);
+        let dbse = !DenseBitSetExtended::from_string(String::from("000"), 2);
+        assert_eq!(dbse.to_bytes(), vec![0x07]);
+
+        assert!(DenseBitSetExtended::new().to_bytes().is_empty());
+    }
+
+    #[test]
+    fn test_from_bytes_dbse() {
+        let bytes = [0xef, 0xbe, 0xad, 0xde, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab];
+        let dbse = DenseBitSetExtended::from_bytes(&bytes);
+        assert_eq!(dbse.get_size(), 80);
+        assert_eq!(dbse.get_word(0), 0x6745_2301_dead_beef);
+        assert_eq!(dbse.get_word(1), 0xab89);
+        assert_eq!(dbse.to_bytes(), bytes.to_vec());
+
+        // Round trip of a bitset whose size is a multiple of 8
+        let dbse = DenseBitSetExtended::from_sorted_positions(&[3, 100, 150, 191]);
+        assert_eq!(DenseBitSetExtended::from_bytes(&dbse.to_bytes()), dbse);
+
+        let empty = DenseBitSetExtended::from_bytes(&[]);
+        assert_eq!(empty.get_size(), 0);
+    }
+
+    #[test]
+    fn test_window_popcounts_dbse() {
+        let dbse: DenseBitSetExtended = [0, 1, 5, 63, 64, 65, 100, 127, 128, 199]
+            .iter()
+            .cloned()
+            .collect();
+        assert_eq!(dbse.get_size(), 200);
+        for &window in [1, 3, 8, 50, 64, 100, 150, 200, 500].iter() {
+            let counts = dbse.window_popcounts(window);
+            assert_eq!(counts.len(), 200_usize.div_ceil(window));
+            // Each block is the difference between the number of set bits before its end and start
+            let before = |p: usize| (0..p).filter(|&i| dbse.get_bit(i)).count() as u32;
+            for (i, &c) in counts.iter().enumerate() {
+                let end = min((i + 1) * window, 200);
+                assert_eq!(c, before(end) - before(i * window));
+            }
+            assert_eq!(counts.iter().sum::<u32>(), dbse.get_weight());
+        }
+        assert!(DenseBitSetExtended::new().window_popcounts(4).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_window_popcounts_zero_dbse() {
+        // Should panic: windows must not be empty
+        DenseBitSetExtended::from_string(String::from("101"), 2).window_popcounts(0);
+    }
+
+    #[test]
+    fn test_count_zeros_dbs() {
+        assert_eq!(DenseBitSet::from_integer(0).count_zeros(), 64);
+        assert_eq!(DenseBitSet::from_integer(u64::MAX).count_zeros(), 0);
+        let dbs = DenseBitSet::from_integer(0xdead_beef);
+        assert_eq!(dbs.count_zeros() + dbs.get_weight(), 64);
+    }
+
+    #[test]
+    fn test_count_zeros_dbse() {
+        let dbse = DenseBitSetExtended::from_sorted_positions(&[0, 64, 99]);
+        assert_eq!(dbse.get_size(), 100);
+        assert_eq!(dbse.count_zeros(), 97);
+
+        // Bits beyond the size are not counted
+        let dbse = !DenseBitSetExtended::from_string(String::from("000"), 2);
+        assert_eq!(dbse.count_zeros(), 0);
+        assert_eq!(
+            DenseBitSetExtended::from_string(String::from("000"), 2).count_zeros(),
+            3
+        );
+        assert_eq!(DenseBitSetExtended::new().count_zeros(), 0);
+    }
+
+    #[test]
+    fn test_rotate_field_dbs() {
+        let orig = DenseBitSet::from_integer(0xffff_0000_ffff_0000);
+        // A 4-bit field 0b0001 at position 20, surrounded by ones and zeros
+        let mut dbs = orig;
+        dbs.insert(20, 4, 0b0001);
+        let neighbours = |d: DenseBitSet| (d.extract(0, 20), d.extract(24, 40));
+
+        let mut rotated = dbs;
+        for shift in 1..10 {
+            rotated.rotate_field(20, 4, 1);
+            assert_eq!(rotated.extract(20, 4), 1 << (shift % 4));
+            assert_eq!(neighbours(rotated), neighbours(dbs));
+        }
+        let mut skip = dbs;
+        skip.rotate_field(20, 4, 6);
+        assert_eq!(skip.extract(20, 4), 0b0100);
+
+        // Full-width fields behave like rotl
+        let mut full = orig;
+        full.rotate_field(0, 64, 68);
+        let mut expected = orig;
+        expected.rotl(4);
+        assert_eq!(full, expected);
+
+        let mut same = orig;
+        same.rotate_field(60, 4, 0);
+        assert_eq!(same, orig);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_rotate_field_dbs() {
+        // Should panic: the field goes beyond the 64 bit limit
+        let mut dbs = DenseBitSet::from_integer(1234);
+        dbs.rotate_field(60, 5, 1);
+    }
+
+    #[test]
+    fn test_last_set_dbs() {
+        assert_eq!(DenseBitSet::from_integer(0b100010).last_set(), 5);
+        assert_eq!(DenseBitSet::from_integer(1).last_set(), 0);
+        assert_eq!(DenseBitSet::from_integer(1 << 63 | 1).last_set(), 63);
+        assert_eq!(DenseBitSet::from_integer(0).last_set(), 64);
+    }
+
+    #[test]
+    fn test_last_set_dbse() {
+        let dbse = DenseBitSetExtended::from_sorted_positions(&[3, 70, 1234]);
+        assert_eq!(dbse.last_set(), 1234);
+        let mut dbse = DenseBitSetExtended::from_sorted_positions(&[3, 64]);
+        dbse.grow_words(5);
+        assert_eq!(dbse.last_set(), 64);
+
+        // Bits beyond the size are ignored
+        let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0xff)) >> 4;
+        assert_eq!(dbse.last_set(), 3);
+
+        // No bit set: the size is returned
+        let mut zero = DenseBitSetExtended::new();
+        zero.grow_words(2);
+        assert_eq!(zero.last_set(), 128);
+        assert_eq!(DenseBitSetExtended::new().last_set(), 0);
+    }
+
+    #[test]
+    fn test_from_words_iter_dbse() {
+        let dbse = DenseBitSetExtended::from_words_iter((0..3).map(|i| i as u64));
+        assert_eq!(dbse.get_size(), 192);
+        assert_eq!(dbse.get_word(0), 0);
+        assert_eq!(dbse.get_word(1), 1);
+        assert_eq!(dbse.get_word(2), 2);
+        assert_eq!(dbse.iter_ones().collect::<Vec<_>>(), vec![64, 129]);
+
+        let empty = DenseBitSetExtended::from_words_iter(std::iter::empty());
+        assert_eq!(empty.get_size(), 0);
+    }
+
+    #[test]
+    fn test_next_prev_set_bit_dbs() {
+        let dbs = DenseBitSet::from_integer(0x8000_0000_0010_0101);
+        let mut positions = vec![];
+        let mut next = dbs.next_set_bit(0);
+        while let Some(p) = next {
+            positions.push(p);
+            next = dbs.next_set_bit(p + 1);
+        }
+        assert_eq!(positions, dbs.iter_ones().collect::<Vec<_>>());
+
+        let mut reversed = vec![];
+        let mut prev = dbs.prev_set_bit(100);
+        while let Some(p) = prev {
+            reversed.push(p);
+            prev = if p == 0 {
+                None
+            } else {
+                dbs.prev_set_bit(p - 1)
+            };
+        }
+        reversed.reverse();
+        assert_eq!(reversed, positions);
+
+        assert_eq!(DenseBitSet::new().next_set_bit(0), None);
+        assert_eq!(DenseBitSet::new().prev_set_bit(63), None);
+        assert_eq!(dbs.next_set_bit(64), None);
+        assert_eq!(dbs.prev_set_bit(62), Some(20));
+    }
+
+    #[test]
+    fn test_next_prev_set_bit_dbse() {
+        let dbse = DenseBitSetExtended::from_sorted_positions(&[0, 3, 63, 64, 70, 500, 1234]);
+        let mut positions = vec![];
+        let mut next = dbse.next_set_bit(0);
+        while let Some(p) = next {
+            positions.push(p);
+            next = dbse.next_set_bit(p + 1);
+        }
+        assert_eq!(positions, dbse.iter_ones().collect::<Vec<_>>());
+
+        let mut reversed = vec![];
+        let mut prev = dbse.prev_set_bit(5000);
+        while let Some(p) = prev {
+            reversed.push(p);
+            prev = if p == 0 {
+                None
+            } else {
+                dbse.prev_set_bit(p - 1)
+            };
+        }
+        reversed.reverse();
+        assert_eq!(reversed, positions);
+
+        assert_eq!(dbse.next_set_bit(71), Some(500));
+        assert_eq!(dbse.prev_set_bit(499), Some(70));
+        assert_eq!(DenseBitSetExtended::new().next_set_bit(0), None);
+        assert_eq!(DenseBitSetExtended::new().prev_set_bit(10), None);
+
+        // Bits beyond the size are ignored
+        let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0xf0)) >> 60;
+        assert_eq!(dbse.prev_set_bit(100), None);
+    }
+
+    #[test]
+    fn test_agreement_dbs() {
+        let dbs = DenseBitSet::from_integer(0xdead_beef_0123_4567);
+        assert_eq!(dbs.agreement(dbs), (64, 0));
+        assert_eq!(dbs.agreement(!dbs), (0, 64));
+        let other = DenseBitSet::from_integer(0x0123_4567);
+        let (same, diff) = dbs.agreement(other);
+        assert_eq!(same + diff, 64);
+        assert_eq!(diff, (dbs ^ other).get_weight());
+    }
+
+    #[test]
+    fn test_agreement_dbse() {
+        let dbse = DenseBitSetExtended::from_sorted_positions(&[0, 5, 64, 99]);
+        assert_eq!(dbse.agreement(&dbse), (100, 0));
+        // The complement is masked to the size
+        assert_eq!(dbse.agreement(&!dbse.clone()), (0, 100));
+
+        let other = DenseBitSetExtended::from_sorted_positions(&[5, 64, 150]);
+        let (same, diff) = dbse.agreement(&other);
+        assert_eq!(same + diff, 151);
+        assert_eq!(diff, 3);
+        assert_eq!(other.agreement(&dbse), (same, diff));
+        assert_eq!(
+            DenseBitSetExtended::new().agreement(&DenseBitSetExtended::new()),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn test_select_dbs() {
+        let dbs = DenseBitSet::from_integer(0b101001);
+        assert_eq!(dbs.select(0), Some(0));
+        assert_eq!(dbs.select(1), Some(3));
+        assert_eq!(dbs.select(2), Some(5));
+        assert_eq!(dbs.select(3), None);
+        assert_eq!(DenseBitSet::new().select(0), None);
+        assert_eq!(DenseBitSet::from_integer(u64::MAX).select(63), Some(63));
+    }
+
+    #[test]
+    fn test_select_dbse() {
+        let positions = [0, 3, 63, 64, 70, 500, 1234];
+        let dbse: DenseBitSetExtended = positions.iter().cloned().collect();
+        for (n, &p) in positions.iter().enumerate() {
+            assert_eq!(dbse.select(n), Some(p));
+        }
+        assert_eq!(dbse.select(positions.len()), None);
+        assert_eq!(DenseBitSetExtended::new().select(0), None);
+
+        let small = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b101001));
+        assert_eq!(small.select(2), Some(5));
+        assert_eq!(small.select(3), None);
+
+        // Bits beyond the size are ignored
+        let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0xf0)) >> 60;
+        assert_eq!(dbse.select(0), None);
+    }
+
+    #[test]
+    fn test_capacity_words_dbse() {
+        let mut dbse = DenseBitSetExtended::with_capacity_words(5);
+        let capacity = dbse.capacity_words();
+        assert!(capacity >= 5);
+        assert_eq!(dbse.get_size(), 0);
+        // Filling the reserved words does not reallocate
+        dbse.set_bit(5 * 64 - 1, true);
+        assert_eq!(dbse.capacity_words(), capacity);
+
+        let mut dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(1));
+        dbse.reserve_exact_words(3);
+        assert!(dbse.capacity_words() >= 4);
+        assert_eq!(dbse.get_size(), 64);
+
+        assert_eq!(
+            DenseBitSetExtended::with_capacity_words(0).capacity_words(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_rank_dbse() {
+        let positions = [0, 3, 63, 64, 70, 127, 128, 500, 1234];
+        let dbse: DenseBitSetExtended = positions.iter().cloned().collect();
+        for n in 0..positions.len() {
+            let p = dbse.select(n).unwrap();
+            assert_eq!(dbse.rank(p), n);
+            assert_eq!(dbse.rank(p + 1), n + 1);
+        }
+        for p in 0..1300 {
+            assert_eq!(dbse.rank(p), positions.iter().filter(|&&q| q < p).count());
+        }
+        assert_eq!(DenseBitSetExtended::new().rank(10), 0);
+
+        // Bits beyond the size are ignored
+        let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0xf3)) >> 60;
+        assert_eq!(dbse.rank(64), 0);
+    }
+
+    #[test]
+    fn test_interleave_n_dbs() {
+        // 21 bits per channel fit in 63 output bits
+        let channels = [
+            DenseBitSet::from_integer(0x1a_bcde),
+            DenseBitSet::from_integer(0x0f_0f0f),
+            DenseBitSet::from_integer(0x1f_ffff),
+        ];
+        let packed = DenseBitSet::interleave_n(&channels);
+        for k in 0..63 {
+            assert_eq!(packed.get_bit(k), channels[k % 3].get_bit(k / 3));
+        }
+        assert!(!packed.get_bit(63));
+        assert_eq!(packed.deinterleave_n(3), channels.to_vec());
+
+        // Any bitset survives the round trip the other way
+        let dbs = DenseBitSet::from_integer(0xdead_beef_0123_4567);
+        for n in 1..=8 {
+            assert_eq!(DenseBitSet::interleave_n(&dbs.deinterleave_n(n)), dbs);
+        }
+        assert_eq!(DenseBitSet::interleave_n(&[dbs]), dbs);
+        assert_eq!(DenseBitSet::interleave_n(&[]), DenseBitSet::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_interleave_n_overflow_dbs() {
+        // Should panic: bit 21 of the second channel would land at position 64
+        let channels = [
+            DenseBitSet::new(),
+            DenseBitSet::from_integer(1 << 21),
+            DenseBitSet::new(),
+        ];
+        DenseBitSet::interleave_n(&channels);
+    }
+
+    #[test]
+    fn test_difference_dbs() {
+        let a = DenseBitSet::from_integer(0xff00_ff00);
+        let b = DenseBitSet::from_integer(0x0ff0_0ff0);
+        assert_eq!(a.difference(b).to_integer(), 0xf000_f000);
+        assert_eq!(a.difference(b), a & !b);
+        assert!(a.difference(a).none());
+        assert_eq!(a.difference(DenseBitSet::new()), a);
+    }
+
+    #[test]
+    fn test_difference_dbse() {
+        let a = DenseBitSetExtended::from_sorted_positions(&[0, 3, 64, 100, 300]);
+        let b = DenseBitSetExtended::from_sorted_positions(&[3, 64, 65]);
+        // `a` is longer than `b`: its bits beyond the size of `b` are kept
+        let d = a.difference(&b);
+        assert_eq!(d.get_size(), a.get_size());
+        assert_eq!(d.iter_ones().collect::<Vec<_>>(), vec![0, 100, 300]);
+
+        // `b` is shorter: the result keeps its size
+        let d = b.difference(&a);
+        assert_eq!(d.get_size(), b.get_size());
+        assert_eq!(d.iter_ones().collect::<Vec<_>>(), vec![65]);
+
+        assert_eq!(a.difference(&a).get_weight(), 0);
+        assert_eq!(a.difference(&DenseBitSetExtended::new()), a);
+        assert_eq!(DenseBitSetExtended::new().difference(&a).get_size(), 0);
+    }
+
+    #[test]
+    fn test_delta_positions_dbse() {
+        let positions = [2, 3, 4, 60, 64, 65, 1000, 1001];
+        let dbse: DenseBitSetExtended = positions.iter().cloned().collect();
+        let deltas = dbse.to_delta_positions();
+        assert_eq!(deltas, vec![2, 1, 1, 56, 4, 1, 935, 1]);
+
+        // The running sums of the deltas are the positions
+        let sums: Vec<usize> = deltas
+            .iter()
+            .scan(0, |acc, &d| {
+                *acc += d;
+                Some(*acc)
+            })
+            .collect();
+        assert_eq!(sums, positions.to_vec());
+
+        assert_eq!(DenseBitSetExtended::from_delta_positions(&deltas), dbse);
+        let zero_first = DenseBitSetExtended::from_delta_positions(&[0, 7]);
+        assert_eq!(zero_first.iter_ones().collect::<Vec<_>>(), vec![0, 7]);
+        assert!(DenseBitSetExtended::new().to_delta_positions().is_empty());
+        assert_eq!(DenseBitSetExtended::from_delta_positions(&[]).get_size(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_from_delta_positions_zero_dbse() {
+        // Should panic: a zero delta would set the same bit twice
+        DenseBitSetExtended::from_delta_positions(&[3, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_from_delta_positions_overflow_dbse() {
+        // Should panic: the second position overflows
+        DenseBitSetExtended::from_delta_positions(&[5, usize::MAX]);
+    }
+
+    #[test]
+    fn test_symmetric_difference_dbs() {
+        let a = DenseBitSet::from_integer(0xff00_ff00);
+        let b = DenseBitSet::from_integer(0x0ff0_0ff0);
+        assert_eq!(a.symmetric_difference(b), a ^ b);
+        assert_eq!(a.symmetric_difference(b), b.symmetric_difference(a));
+        assert!(a.symmetric_difference(a).none());
+    }
+
+    #[test]
+    fn test_symmetric_difference_dbse() {
+        let a = DenseBitSetExtended::from_sorted_positions(&[0, 3, 64, 100, 300]);
+        let b = DenseBitSetExtended::from_sorted_positions(&[3, 64, 65]);
+        let d = a.symmetric_difference(&b);
+        assert_eq!(d, a.clone() ^ b.clone());
+        assert_eq!(d.get_size(), 301);
+        assert_eq!(d.iter_ones().collect::<Vec<_>>(), vec![0, 65, 100, 300]);
+        assert_eq!(b.symmetric_difference(&a), d);
+
+        assert_eq!(a.symmetric_difference(&a).get_weight(), 0);
+        assert_eq!(a.symmetric_difference(&a).get_size(), a.get_size());
+        assert_eq!(DenseBitSetExtended::new().symmetric_difference(&a), a);
+    }
+
+    #[test]
+    fn test_popcount_even_odd_dbs() {
+        for &v in [
+            0,
+            u64::MAX,
+            0x5555_5555_5555_5555,
+            0xAAAA_AAAA_AAAA_AAAA,
+            0xdead_beef_0123_4567,
+            1 << 63,
+        ]
+        .iter()
+        {
+            let dbs = DenseBitSet::from_integer(v);
+            let even = (0..64).step_by(2).filter(|&i| dbs.get_bit(i)).count() as u32;
+            let odd = (1..64).step_by(2).filter(|&i| dbs.get_bit(i)).count() as u32;
+            assert_eq!(dbs.popcount_even_odd(), (even, odd));
+        }
+        assert_eq!(
+            DenseBitSet::from_integer(0x5555_5555_5555_5555).popcount_even_odd(),
+            (32, 0)
+        );
+    }
+
+    #[test]
+    fn test_is_subset_dbs() {
+        let a = DenseBitSet::from_integer(0x0f00);
+        let b = DenseBitSet::from_integer(0xff00);
+        assert!(a.is_subset(b) && b.is_superset(a));
+        assert!(!b.is_subset(a) && !a.is_superset(b));
+        assert!(a.is_subset(a) && a.is_superset(a));
+        assert!(DenseBitSet::new().is_subset(a));
+        assert!(a.is_superset(DenseBitSet::new()));
+        assert!(!DenseBitSet::from_integer(1).is_subset(b));
+    }
+
+    #[test]
+    fn test_is_subset_dbse() {
+        let small = DenseBitSetExtended::from_sorted_positions(&[3, 64]);
+        let large = DenseBitSetExtended::from_sorted_positions(&[3, 64, 65, 1000]);
+        assert!(small.is_subset(&large) && large.is_superset(&small));
+        assert!(!large.is_subset(&small) && !small.is_superset(&large));
+        assert!(large.is_subset(&large) && large.is_superset(&large));
+
+        // The empty set is a subset of everything, whatever the sizes
+        let mut zeros = DenseBitSetExtended::new();
+        zeros.grow_words(30);
+        assert!(DenseBitSetExtended::new().is_subset(&small));
+        assert!(zeros.is_subset(&small));
+        assert!(small.is_superset(&zeros));
+        assert!(!small.is_subset(&zeros));
+
+        // A longer bitset can be a subset of a shorter one if its extra bits are zeros
+        let mut padded = small.clone();
+        padded.grow_words(30);
+        assert!(padded.is_subset(&small) && small.is_subset(&padded));
+    }
+
+    #[test]
+    fn test_and_not_dbse() {
+        let a = DenseBitSetExtended::from_sorted_positions(&[0, 3, 64, 100, 127]);
+        let b = DenseBitSetExtended::from_sorted_positions(&[3, 64, 65, 127]);
+        assert_eq!(a.get_size(), b.get_size());
+        assert_eq!(a.and_not(&b), a.clone() & !b.clone());
+
+        // `other` shorter than `self`
+        let short = DenseBitSetExtended::from_sorted_positions(&[3]);
+        let mut d = a.clone();
+        d.and_not_assign(&short);
+        assert_eq!(d, a.and_not(&short));
+        assert_eq!(d.get_size(), a.get_size());
+        assert_eq!(d.iter_ones().collect::<Vec<_>>(), vec![0, 64, 100, 127]);
+
+        // `other` longer than `self`
+        let long = DenseBitSetExtended::from_sorted_positions(&[0, 100, 5000]);
+        let mut d = a.clone();
+        d.and_not_assign(&long);
+        assert_eq!(d, a.and_not(&long));
+        assert_eq!(d.get_size(), a.get_size());
+        assert_eq!(d.iter_ones().collect::<Vec<_>>(), vec![3, 64, 127]);
+
+        // No complement of an empty bitset is needed
+        let mut e = a.clone();
+        e.and_not_assign(&DenseBitSetExtended::new());
+        assert_eq!(e, a);
+        let mut empty = DenseBitSetExtended::new();
+        empty.and_not_assign(&a);
+        assert_eq!(empty.get_size(), 0);
+    }
+
+    #[test]
+    fn test_is_disjoint_dbs() {
+        let a = DenseBitSet::from_integer(0xf0f0);
+        assert!(a.is_disjoint(DenseBitSet::from_integer(0x0f0f)));
+        assert!(!a.is_disjoint(DenseBitSet::from_integer(0x0010)));
+        assert!(a.is_disjoint(DenseBitSet::new()));
+        assert!(!a.is_disjoint(a));
+    }
+
+    #[test]
+    fn test_is_disjoint_dbse() {
+        // Bits set in the same word
+        let a = DenseBitSetExtended::from_sorted_positions(&[1, 3, 70]);
+        let b = DenseBitSetExtended::from_sorted_positions(&[2, 4, 71]);
+        assert!(a.is_disjoint(&b) && b.is_disjoint(&a));
+        let c = DenseBitSetExtended::from_sorted_positions(&[2, 3]);
+        assert!(!a.is_disjoint(&c) && !c.is_disjoint(&a));
+
+        // Bits set in words far apart
+        let low = DenseBitSetExtended::from_sorted_positions(&[5]);
+        let high = DenseBitSetExtended::from_sorted_positions(&[5000]);
+        assert!(low.is_disjoint(&high) && high.is_disjoint(&low));
+
+        assert!(a.is_disjoint(&DenseBitSetExtended::new()));
+        assert!(!a.is_disjoint(&a));
+    }
+
+    #[test]
+    fn test_swap_bit_pairs_dbs() {
+        let dbs = DenseBitSet::from_integer(0xdead_beef_0123_4567);
+        let swapped = dbs.swap_bit_pairs();
+        for i in 0..64 {
+            assert_eq!(swapped.get_bit(i), dbs.get_bit(i ^ 1));
+        }
+        assert_eq!(swapped.swap_bit_pairs(), dbs);
+        assert_eq!(
+            DenseBitSet::from_integer(0x5555_5555_5555_5555)
+                .swap_bit_pairs()
+                .to_integer(),
+            0xAAAA_AAAA_AAAA_AAAA
+        );
+    }
+
+    #[test]
+    fn test_reverse_within_nibbles_dbs() {
+        let dbs = DenseBitSet::from_integer(0xdead_beef_0123_4567);
+        let reversed = dbs.reverse_within_nibbles();
+        for i in 0..64 {
+            // Position `4n + j` goes to `4n + 3 - j`
+            assert_eq!(reversed.get_bit(i), dbs.get_bit((i & !3) + 3 - (i & 3)));
+        }
+        assert_eq!(reversed.reverse_within_nibbles(), dbs);
+        assert_eq!(
+            DenseBitSet::from_integer(0x0123_4567_89ab_cdef)
+                .reverse_within_nibbles()
+                .to_integer(),
+            0x084c_2a6e_195d_3b7f
+        );
+    }
+
+    #[test]
+    fn test_display_dbs() {
+        let dbs = DenseBitSet::from_integer(65536);
+        assert_eq!(
+            format!("{}", dbs),
+            "0000000000000000000000000000000000000000000000010000000000000000"
+        );
+        // Formatting does not move the bitset
+        assert_eq!(format!("{}", dbs), dbs.to_string());
+    }
+
+    #[test]
+    fn test_display_dbse() {
+        let dbse = DenseBitSetExtended::from_sorted_positions(&[0, 64, 99]);
+        let s = format!("{}", dbse);
+        assert_eq!(s.len(), 128);
+        assert_eq!(s, dbse.clone().to_string());
+        assert_eq!(&s[..64], &format!("{:064b}", (1u64 << 35) | 1)[..]);
+        assert_eq!(&s[64..], &format!("{:064b}", 1)[..]);
+        assert_eq!(format!("{}", DenseBitSetExtended::new()), "0".repeat(64));
+    }
+
+    #[test]
+    fn test_intersection_counts_dbse() {
+        let query: DenseBitSetExtended = (0..1000).filter(|p| p % 3 == 0).collect();
+        let candidates: Vec<DenseBitSetExtended> = (1..20)
+            .map(|k| (0..(k * 100)).filter(|p| p % k == 0).collect())
+            .collect();
+        let counts = query.intersection_counts(&candidates);
+        assert_eq!(counts.len(), candidates.len());
+        for (c, &count) in candidates.iter().zip(counts.iter()) {
+            assert_eq!(count, query.intersection_count(c));
+            assert_eq!(count, (query.clone() & c.clone()).get_weight());
+        }
+        assert_eq!(query.intersection_count(&query), query.get_weight());
+        assert!(query.intersection_counts(&[]).is_empty());
+        assert_eq!(
+            DenseBitSetExtended::new().intersection_counts(&candidates),
+            vec![0; candidates.len()]
+        );
+    }
+
+    #[test]
+    fn test_hex_dbs() {
+        let dbs = DenseBitSet::from_string("deadc0fee", 16);
+        assert_eq!(format!("{:x}", dbs), "deadc0fee");
+        assert_eq!(format!("{:X}", dbs), "DEADC0FEE");
+        assert_eq!(format!("{:#018x}", dbs), "0x0000000deadc0fee");
+        assert_eq!(DenseBitSet::from_string(&format!("{:x}", dbs), 16), dbs);
+        assert_eq!(format!("{:x}", DenseBitSet::new()), "0");
+    }
+
+    #[test]
+    fn test_hex_dbse() {
+        let hex = "f001eddadf411eddec0de5ca1ab1ec0feefeeb1e01dc0b01";
+        let dbse = DenseBitSetExtended::from_string(String::from(hex), 16);
+        assert_eq!(format!("{:x}", dbse), hex);
+        assert_eq!(format!("{:X}", dbse), hex.to_uppercase());
+        assert_eq!(format!("{:#x}", dbse), format!("0x{}", hex));
+
+        // Leading zeros are dropped, including in the middle of a word
+        let padded = "0000000000000000000000000000000000000000abc00000000000000000000000001";
+        let dbse = DenseBitSetExtended::from_string(String::from(padded), 16);
+        let formatted = format!("{:x}", dbse);
+        assert_eq!(formatted, padded.trim_start_matches('0'));
+        assert_eq!(
+            DenseBitSetExtended::from_string(formatted, 16).to_sorted_positions(),
+            dbse.to_sorted_positions()
+        );
+        assert_eq!(
+            format!(
+                "{:>8x}",
+                DenseBitSetExtended::from_string(String::from("1f"), 16)
+            ),
+            "      1f"
+        );
+
+        // Bits beyond the size are ignored
+        let dbse = !DenseBitSetExtended::from_string(String::from("000000"), 2);
+        assert_eq!(format!("{:x}", dbse), "3f");
+        assert_eq!(format!("{:x}", DenseBitSetExtended::new()), "0");
+    }
+
+    #[test]
+    fn test_i64_dbs() {
+        for &v in [0, 1, -1, 42, -42, i64::MIN, i64::MAX].iter() {
+            let dbs = DenseBitSet::from_i64(v);
+            assert_eq!(dbs.to_i64(), v);
+            assert_eq!(dbs.to_integer(), v as u64);
+            assert_eq!(dbs.get_bit(63), v < 0);
+        }
+        let min = DenseBitSet::from_i64(i64::MIN);
+        assert_eq!(min.get_weight(), 1);
+        assert_eq!(min.first_set(), 63);
+        assert_eq!(DenseBitSet::from_i64(-1).get_weight(), 64);
+        assert_eq!(DenseBitSet::from_integer(1 << 63).to_i64(), i64::MIN);
+    }
+
+    #[test]
+    fn test_binary_dbs() {
+        let dbs = DenseBitSet::from_integer(0b101101);
+        assert_eq!(format!("{:b}", dbs), "101101");
+        assert_eq!(format!("{:#b}", dbs), "0b101101");
+        assert_eq!(format!("{:08b}", dbs), "00101101");
+        assert_eq!(format!("{:#010b}", dbs), "0b00101101");
+        // A width smaller than the natural length does not truncate
+        assert_eq!(format!("{:03b}", dbs), "101101");
+        assert_eq!(format!("{:>8b}", dbs), "  101101");
+        assert_eq!(format!("{:064b}", dbs), dbs.to_string());
+        assert_eq!(format!("{:b}", DenseBitSet::new()), "0");
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter_ones_dbse() {
+        use rayon::prelude::*;
+
+        let dbse: DenseBitSetExtended = (0..50000).filter(|p| p % 7 == 0 || p % 64 == 63).collect();
+        let mut positions: Vec<usize> = dbse.par_iter_ones().collect();
+        positions.sort();
+        assert_eq!(positions, dbse.iter_ones().collect::<Vec<_>>());
+
+        // Bits beyond the size are not yielded
+        let dbse = !DenseBitSetExtended::from_string(String::from("000"), 2);
+        let mut positions: Vec<usize> = dbse.par_iter_ones().collect();
+        positions.sort();
+        assert_eq!(positions, vec![0, 1, 2]);
+        assert_eq!(DenseBitSetExtended::new().par_iter_ones().count(), 0);
+    }
+
+    #[test]
+    fn test_set_range_dbs() {
+        let mut dbs = DenseBitSet::new();
+        dbs.set_range(4, 8, true);
+        assert_eq!(dbs.to_integer(), 0b1111_0000);
+        dbs.set_range(0, 64, true);
+        assert!(dbs.all());
+        dbs.set_range(30, 40, false);
+        assert_eq!(dbs.to_integer(), !(0x3ff << 30));
+        dbs.set_range(63, 64, false);
+        assert!(!dbs.get_bit(63));
+        dbs.set_range(10, 10, false);
+        assert_eq!(dbs.to_integer(), !(0x3ff << 30) & !(1 << 63));
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_set_range_dbs() {
+        // Should panic: the range goes beyond the 64 bit limit
+        let mut dbs = DenseBitSet::new();
+        dbs.set_range(60, 65, true);
+    }
+
+    #[test]
+    fn test_set_range_dbse() {
+        // Ranges spanning word boundaries
+        let mut dbse = DenseBitSetExtended::new();
+        dbse.set_range(60, 200, true);
+        assert_eq!(dbse.get_size(), 200);
+        assert_eq!(
+            dbse.iter_ones().collect::<Vec<_>>(),
+            (60..200).collect::<Vec<_>>()
+        );
+
+        dbse.set_range(62, 130, false);
+        assert_eq!(dbse.get_size(), 200);
+        assert_eq!(
+            dbse.iter_ones().collect::<Vec<_>>(),
+            (60..62).chain(130..200).collect::<Vec<_>>()
+        );
+
+        // Clearing beyond the size does not grow the bitset
+        dbse.set_range(150, 1000, false);
+        assert_eq!(dbse.get_size(), 200);
+        assert_eq!(dbse.get_weight(), 2 + 20);
+
+        // Setting grows it, without revealing stale bits
+        let mut dbse = !DenseBitSetExtended::from_string(String::from("000"), 2);
+        dbse.set_range(100, 101, true);
+        assert_eq!(dbse.get_size(), 101);
+        assert_eq!(dbse.iter_ones().collect::<Vec<_>>(), vec![0, 1, 2, 100]);
+
+        let mut empty = DenseBitSetExtended::new();
+        empty.set_range(0, 64, false);
+        assert_eq!(empty.get_size(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_set_range_too_large_dbse() {
+        let mut dbse = DenseBitSetExtended::new();
+        dbse.set_range(0, 100_000, true); // Should panic: the bitset would exceed the size limit
+    }
+
+    #[test]
+    fn test_range_mask_dbs() {
+        assert_eq!(DenseBitSet::range_mask(4, 8).to_integer(), 0b1111_0000);
+        assert_eq!(
+            DenseBitSet::range_mask_inclusive(4, 7).to_integer(),
+            0b1111_0000
+        );
+        assert_eq!(DenseBitSet::range_mask(63, 64).to_integer(), 1 << 63);
+        assert_eq!(DenseBitSet::range_mask(0, 64).to_integer(), u64::MAX);
+        assert_eq!(
+            DenseBitSet::range_mask_inclusive(0, 63).to_integer(),
+            u64::MAX
+        );
+        assert_eq!(DenseBitSet::range_mask(1, 64).to_integer(), u64::MAX - 1);
+        assert!(DenseBitSet::range_mask(64, 64).none());
+        assert!(DenseBitSet::range_mask(10, 10).none());
+        for start in 0..64 {
+            for end in start..=64 {
+                let mask = DenseBitSet::range_mask(start, end);
+                assert_eq!(mask.get_weight() as usize, end - start);
+                assert!((start..end).all(|i| mask.get_bit(i)));
+            }
+        }
+
+        const FIELD: DenseBitSet = DenseBitSet::range_mask(16, 32);
+        assert_eq!(FIELD.to_integer(), 0xffff_0000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_range_mask_inclusive_dbs() {
+        // Should panic: bit 64 is beyond the 64 bit limit
+        DenseBitSet::range_mask_inclusive(60, 64);
+    }
+
+    #[test]
+    fn test_flip_range_dbs() {
+        let orig = DenseBitSet::from_integer(0xdead_beef_0123_4567);
+        let mut dbs = orig;
+        dbs.flip_range(20, 50);
+        for i in 0..64 {
+            assert_eq!(dbs.get_bit(i), orig.get_bit(i) ^ (20..50).contains(&i));
+        }
+        dbs.flip_range(20, 50);
+        assert_eq!(dbs, orig);
+
+        dbs.flip_range(0, 64);
+        assert_eq!(dbs, !orig);
+        dbs.flip_range(5, 5);
+        assert_eq!(dbs, !orig);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_flip_range_dbs() {
+        // Should panic: the range is reversed
+        let mut dbs = DenseBitSet::new();
+        dbs.flip_range(8, 4);
+    }
+
+    #[test]
+    fn test_flip_range_dbse() {
+        let orig = DenseBitSetExtended::from_sorted_positions(&[0, 60, 63, 64, 70, 199]);
+        // A range crossing word boundaries
+        let mut dbse = orig.clone();
+        dbse.flip_range(62, 130);
+        assert_eq!(dbse.get_size(), orig.get_size());
+        for i in 0..200 {
+            assert_eq!(dbse.get_bit(i), orig.get_bit(i) ^ (62..130).contains(&i));
+        }
+        dbse.flip_range(62, 130);
+        assert_eq!(dbse, orig);
+
+        // Beyond the size: the bitset grows, and flipping back leaves zeros
+        dbse.flip_range(190, 300);
+        assert_eq!(dbse.get_size(), 300);
+        assert_eq!(dbse.rank(300), orig.get_weight() as usize - 1 + 109);
+        dbse.flip_range(190, 300);
+        assert_eq!(dbse.get_size(), 300);
+        assert!(dbse.numeric_eq(&orig));
+
+        dbse.flip_range(10, 10);
+        assert_eq!(dbse.get_size(), 300);
+    }
+
+    #[test]
+    #[should_panic]
+    fn catch_flip_range_too_large_dbse() {
+        let mut dbse = DenseBitSetExtended::new();
+        dbse.flip_range(0, 100_000); // Should panic: the bitset would exceed the size limit
+    }
+
     // Test for README.md source code
 
     #[test]
@@ -1,7 +1,9 @@
 use crate::bitset::BitSet;
 
+use std::collections::HashSet;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::num::ParseIntError;
 
 /// Overload of &, &=, |, |=, ^, ^=, !, <<, <<=, >>, >>=
 use std::ops::{
@@ -13,260 +15,1832 @@ use std::ops::{
 ///
 /// This structure implements `BitSet, Clone, Copy, Default, Debug, Hash, PartialEq, Eq` and bit operations.
 #[derive(Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct DenseBitSet {
     state: u64,
 }
 
+/// Compile-time check of a bit width given as a const generic parameter.
+///
+/// Referring to `CHECK` fails to compile when `N > 64` (this avoids inline `const` blocks, which
+/// require a recent compiler).
+struct BitWidth<const N: usize>;
+
+impl<const N: usize> BitWidth<N> {
+    const CHECK: () = assert!(
+        N <= 64,
+        "This implementation is currently limited to 64 bit bitsets."
+    );
+}
+
 impl DenseBitSet {
+    /// The empty bitset, with all bits set to `false`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// const MASK: DenseBitSet = DenseBitSet::EMPTY;
+    ///
+    /// assert!(MASK.none());
+    /// ```
+    pub const EMPTY: Self = Self { state: 0 };
+
+    /// The full bitset, with all bits set to `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// const MASK: DenseBitSet = DenseBitSet::FULL;
+    ///
+    /// assert!(MASK.all());
+    /// ```
+    pub const FULL: Self = Self { state: u64::MAX };
+
     /// Returns a new empty `DenseBitSet`.
     ///
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
     ///
-    /// let mut bs = DenseBitSet::new();
+    /// let mut bs = DenseBitSet::new();
+    /// ```
+    pub const fn new() -> Self {
+        Self { state: 0 }
+    }
+
+    /// Generates a bitset from an integer (little endian convention).
+    ///
+    /// This function can be used in `const` contexts.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(1234567890);
+    ///
+    /// const MASK: DenseBitSet = DenseBitSet::from_integer(0xff);
+    /// ```
+    pub const fn from_integer(i: u64) -> Self {
+        Self { state: i }
+    }
+
+    /// Generates a bitset from a string and a base (little endian convention).
+    ///
+    /// The `base` must be an integer between 2 and 32.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let mut bs1 = DenseBitSet::from_string("101010", 2);
+    /// let mut bs2 = DenseBitSet::from_string("2a", 16);
+    ///
+    /// assert_eq!(bs1,bs2);
+    /// ```
+    ///
+    /// # Panics
+    ///  
+    /// This function will panic if an incorrect `base` is provided or if invalid
+    /// characters are found when parsing.
+    pub fn from_string(s: &str, base: u32) -> Self {
+        assert!((2..=32).contains(&base), "Only supports base from 2 to 32");
+        let val = u64::from_str_radix(s, base);
+        let res: u64 = val.expect("Failed to parse string");
+        Self { state: res }
+    }
+
+    /// Returns an integer representing the bitset (little endian convention).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(1234);
+    ///
+    /// assert_eq!(bs.to_integer(), 1234);
+    /// ```
+    pub const fn to_integer(self) -> u64 {
+        self.state
+    }
+
+    /// Returns an integer representation of the bitset starting at the given `position` with given `length` (little endian convention).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b11110101010010);
+    /// let value = bs.extract(5,6);
+    ///
+    /// assert_eq!(value, 42);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `length` is zero or if one tries to
+    /// access a bit beyond the 64 bit limit (i.e., `position + length > 64`).
+    pub const fn extract(self, position: usize, length: usize) -> u64 {
+        assert!(
+            position + length <= 64,
+            "This implementation is currently limited to 64 bit bitsets."
+        );
+        assert!(length > 0, "Cannot extract a zero-width slice.");
+        if length < 64 {
+            (self.state >> position) & ((1 << length) - 1)
+        } else {
+            // This special branch is to avoid overflowing when masking
+            self.state >> position
+        }
+    }
+
+    /// Returns nothing, mutates the `DenseBitSet` to insert `value` at the given `position`.
+    ///
+    /// Note that `value` is treated as a `length`-bit integer (little endian convention);
+    /// if necessary, `value` is padded with zeros (or truncated) to be of the correct length.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let mut bs = DenseBitSet::new();
+    /// bs.insert(10, 8, 0b10101011);
+    /// bs.insert(3,1,1);
+    ///
+    /// assert_eq!(bs.to_integer(), 0b101010110000001000)
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `length` is zero, or if one tries to
+    /// insert a bit beyond the 64 bit limit (i.e. `position + length > 64`)
+    pub fn insert(&mut self, position: usize, length: usize, value: u64) {
+        assert!(
+            position + length <= 64,
+            "This implementation is currently limited to 64 bit bitsets."
+        );
+        assert!(length > 0, "Cannot insert zero-width slice");
+        if length < 64 {
+            let mut u = u64::MAX;
+            u ^= ((1 << length) - 1) << position;
+            self.state &= u;
+            self.state |= value << position;
+        } else {
+            self.state = value;
+        }
+    }
+
+    /// Returns `true` if and only if all bits are set to `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    /// use rust_dense_bitset::BitSet;
+    ///
+    /// let mut bs = DenseBitSet::from_integer(u64::MAX);
+    ///
+    /// assert!(bs.all());
+    ///
+    /// bs.set_bit(28,false);
+    /// bs.all(); // -> false
+    /// ```
+    pub const fn all(self) -> bool {
+        self.state == u64::MAX
+    }
+
+    /// Returns `true` if at least one of the bits is set to `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    /// use rust_dense_bitset::BitSet;
+    ///
+    /// let mut bs = DenseBitSet::from_integer(2048);
+    ///
+    /// assert!(bs.any());
+    ///
+    /// bs.set_bit(11,false);
+    /// bs.any(); // -> false
+    /// ```
+    pub const fn any(self) -> bool {
+        self.state > 0
+    }
+
+    /// Returns `true` if all the bits are set to `false`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    /// use rust_dense_bitset::BitSet;
+    ///
+    /// let mut bs = DenseBitSet::from_integer(2048);
+    /// bs.set_bit(11,false);
+    ///
+    /// assert!(bs.none());
+    /// ```
+    pub const fn none(self) -> bool {
+        !self.any()
+    }
+
+    /// Returns a bit-reversed `DenseBitSet`.
+    ///
+    /// This method is using a constant time bit reversal algorithm for 64 bits integers.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b11110001);
+    /// let bs2 = bs.reverse();
+    ///
+    /// assert_eq!(bs2.to_integer(), 0b1000111100000000000000000000000000000000000000000000000000000000);
+    /// ```
+    pub const fn reverse(self) -> Self {
+        let mut v = self.state;
+        v = ((v >> 1) & 0x5555555555555555_u64) | ((v & 0x5555555555555555_u64) << 1);
+        v = ((v >> 2) & 0x3333333333333333_u64) | ((v & 0x3333333333333333_u64) << 2);
+        v = ((v >> 4) & 0x0F0F0F0F0F0F0F0F_u64) | ((v & 0x0F0F0F0F0F0F0F0F_u64) << 4);
+
+        Self {
+            state: v.swap_bytes(),
+        }
+    }
+
+    /// Right rotation of `shift` bits.
+    ///
+    /// Shifts the bits to the right, wrapping the truncated bits to the end of the bitset.
+    ///
+    /// The rotation is done in-place, so the bitset needs to be mutable.
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let mut bs = DenseBitSet::from_integer(0b111000111000111000111);
+    /// bs.rotr(10);
+    ///
+    /// assert_eq!(bs.to_integer(), 0b111000111000000000000000000000000000000000000000000011100011100 );
+    /// ```
+    pub fn rotr(&mut self, shift: u32) {
+        self.state = self.state.rotate_right(shift);
+    }
+
+    /// Left rotation of `shift` bits.
+    ///
+    /// Shifts the bits to the left, wrapping the truncated bits to the beginning of the bitset.
+    ///  
+    /// The rotation is done in place, so the bitset needs to be mutable.
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let mut bs = DenseBitSet::from_integer(0b111000111000111000111);
+    /// bs.rotl(10);
+    ///
+    /// assert_eq!(bs.to_integer(), 0b1110001110001110001110000000000 );
+    /// ```
+    pub fn rotl(&mut self, shift: u32) {
+        self.state = self.state.rotate_left(shift);
+    }
+
+    /// Returns the position of the first set bit (little endian convention)
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSet;
+    /// let dbs = DenseBitSet::from_integer(256);
+    /// println!("{}", dbs.first_set());
+    /// ```
+    pub const fn first_set(self) -> u32 {
+        self.state.trailing_zeros()
+    }
+
+    /// Returns the position of the last set bit (little endian convention), or 64 if no bit is set
+    /// (matching `first_set`).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// assert_eq!(DenseBitSet::from_integer(0b100010).last_set(), 5);
+    /// assert_eq!(DenseBitSet::new().last_set(), 64);
+    /// ```
+    pub const fn last_set(self) -> usize {
+        if self.state == 0 {
+            64
+        } else {
+            63 - self.state.leading_zeros() as usize
+        }
+    }
+
+    /// Gathers the bits located at the positions set in `mask` into the low bits of the result
+    /// (software implementation of the parallel bit extract, or PEXT, instruction).
+    ///
+    /// Unlike `extract`, the selected bits need not be contiguous.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b10101);
+    /// let mask = DenseBitSet::from_integer(0b11100);
+    ///
+    /// assert_eq!(bs.compress(mask).to_integer(), 0b101);
+    /// ```
+    pub const fn compress(self, mask: Self) -> Self {
+        let mut m = mask.state;
+        let mut res = 0;
+        let mut k = 0;
+        while m != 0 {
+            // Isolate the lowest bit of the mask, then clear it
+            let lowest = m & m.wrapping_neg();
+            if self.state & lowest != 0 {
+                res |= 1 << k;
+            }
+            k += 1;
+            m &= m - 1;
+        }
+        Self { state: res }
+    }
+
+    /// Returns the set of positions of the bits set to `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b100101);
+    /// let positions = bs.to_hashset();
+    ///
+    /// assert_eq!(positions.len(), 3);
+    /// assert!(positions.contains(&5));
+    /// ```
+    pub fn to_hashset(self) -> HashSet<usize> {
+        let mut positions = HashSet::with_capacity(self.state.count_ones() as usize);
+        let mut v = self.state;
+        while v != 0 {
+            positions.insert(v.trailing_zeros() as usize);
+            v &= v - 1;
+        }
+        positions
+    }
+
+    /// Generates a bitset whose set bits are located at the given `positions`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    /// use std::collections::HashSet;
+    ///
+    /// let positions: HashSet<usize> = [0, 2, 5].iter().cloned().collect();
+    /// let bs = DenseBitSet::from_hashset(&positions);
+    ///
+    /// assert_eq!(bs.to_integer(), 0b100101);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if any position is beyond the 64 bit limit.
+    pub fn from_hashset(positions: &HashSet<usize>) -> Self {
+        let mut state = 0;
+        for &p in positions {
+            assert!(
+                p < 64,
+                "This implementation is currently limited to 64 bit bitsets."
+            );
+            state |= 1 << p;
+        }
+        Self { state }
+    }
+
+    /// Generates a bitset by tiling the low `period` bits of `pattern` across all 64 bits.
+    ///
+    /// If `period` does not divide 64, the last copy of the pattern is truncated.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::replicate(0b01, 2);
+    ///
+    /// assert_eq!(bs.to_integer(), 0x5555555555555555);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `period` is zero or larger than 64.
+    pub const fn replicate(pattern: u64, period: usize) -> Self {
+        assert!(
+            period <= 64,
+            "This implementation is currently limited to 64 bit bitsets."
+        );
+        assert!(period > 0, "Cannot replicate a zero-width pattern.");
+        if period == 64 {
+            return Self { state: pattern };
+        }
+
+        let mut state = pattern & ((1 << period) - 1);
+        let mut width = period;
+        while width < 64 {
+            // Double the number of copies at each step
+            state |= state << width;
+            width *= 2;
+        }
+        Self { state }
+    }
+
+    /// Returns the position of the `n`-th set bit (starting from zero, little endian convention),
+    /// or `None` if fewer than `n + 1` bits are set.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b101100);
+    ///
+    /// assert_eq!(bs.nth_set_bit(0), Some(2));
+    /// assert_eq!(bs.nth_set_bit(2), Some(5));
+    /// assert_eq!(bs.nth_set_bit(3), None);
+    /// ```
+    pub const fn nth_set_bit(self, n: usize) -> Option<usize> {
+        if n >= self.state.count_ones() as usize {
+            return None;
+        }
+        let mut v = self.state;
+        let mut i = 0;
+        while i < n {
+            // Clear the lowest set bit
+            v &= v - 1;
+            i += 1;
+        }
+        Some(v.trailing_zeros() as usize)
+    }
+
+    /// Returns the position of the `n`-th set bit (starting from zero), or `None` if fewer than
+    /// `n + 1` bits are set. This is the `select` operation of succinct data structures, and the
+    /// same as `nth_set_bit`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b101001);
+    ///
+    /// assert_eq!(bs.select(1), Some(3));
+    /// assert_eq!(bs.select(3), None);
+    /// ```
+    pub const fn select(self, n: usize) -> Option<usize> {
+        self.nth_set_bit(n)
+    }
+
+    /// Returns the number of set bits at positions strictly below `position`, that is the whole
+    /// weight of the bitset if `position` is 64 or more.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b101101);
+    ///
+    /// assert_eq!(bs.rank(3), 2);
+    /// assert_eq!(bs.rank(64), 4);
+    /// ```
+    pub const fn rank(self, position: usize) -> usize {
+        if position < 64 {
+            (self.state & ((1 << position) - 1)).count_ones() as usize
+        } else {
+            // This special branch is to avoid overflowing when masking
+            self.state.count_ones() as usize
+        }
+    }
+
+    /// Generates a bitset whose only possibly set bit is at `position`, set if and only if `b` is `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// assert_eq!(DenseBitSet::from_bool(true, 4).to_integer(), 0b10000);
+    /// assert!(DenseBitSet::from_bool(false, 4).none());
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `position` is beyond the 64 bit limit.
+    pub const fn from_bool(b: bool, position: usize) -> Self {
+        assert!(
+            position < 64,
+            "This implementation is currently limited to 64 bit bitsets."
+        );
+        Self {
+            state: (b as u64) << position,
+        }
+    }
+
+    /// Returns the bitset if `cond` is `true`, and an empty bitset otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(1234);
+    ///
+    /// assert_eq!(bs.mask_if(true), bs);
+    /// assert!(bs.mask_if(false).none());
+    /// ```
+    pub const fn mask_if(self, cond: bool) -> Self {
+        Self {
+            state: self.state & (cond as u64).wrapping_neg(),
+        }
+    }
+
+    /// Returns the two's complement negation of the bitset (i.e. `!self + 1`, wrapping around).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(1);
+    ///
+    /// assert!(bs.wrapping_neg().all());
+    /// ```
+    pub const fn wrapping_neg(self) -> Self {
+        Self {
+            state: self.state.wrapping_neg(),
+        }
+    }
+
+    /// Returns the absolute difference between the integer values of the two bitsets.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs1 = DenseBitSet::from_integer(12);
+    /// let bs2 = DenseBitSet::from_integer(54);
+    ///
+    /// assert_eq!(bs1.abs_diff(bs2).to_integer(), 42);
+    /// ```
+    pub const fn abs_diff(self, other: Self) -> Self {
+        Self {
+            state: self.state.abs_diff(other.state),
+        }
+    }
+
+    /// Returns a bitset whose bit `i` is the bit of `self` at position `indices[i]`.
+    ///
+    /// Indices may be repeated, and bits beyond `indices.len()` are set to `false`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b10000001);
+    ///
+    /// assert_eq!(bs.gather(&[7, 1, 0]).to_integer(), 0b101);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if more than 64 indices are provided, or if an index is beyond
+    /// the 64 bit limit.
+    pub fn gather(self, indices: &[usize]) -> Self {
+        assert!(
+            indices.len() <= 64,
+            "This implementation is currently limited to 64 bit bitsets."
+        );
+        let mut state = 0;
+        for (i, &idx) in indices.iter().enumerate() {
+            assert!(
+                idx < 64,
+                "This implementation is currently limited to 64 bit bitsets."
+            );
+            state |= ((self.state >> idx) & 1) << i;
+        }
+        Self { state }
+    }
+
+    /// Returns the matrix of pairwise Hamming distances between the given bitsets, where element
+    /// `[i][j]` is the number of bits that differ between `sets[i]` and `sets[j]`.
+    ///
+    /// Note: the computation requires `O(n²)` time and memory for `n` bitsets.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let sets = [DenseBitSet::from_integer(0b0011), DenseBitSet::from_integer(0b0110)];
+    /// let distances = DenseBitSet::pairwise_hamming(&sets);
+    ///
+    /// assert_eq!(distances, vec![vec![0, 2], vec![2, 0]]);
+    /// ```
+    pub fn pairwise_hamming(sets: &[Self]) -> Vec<Vec<u32>> {
+        let n = sets.len();
+        let mut distances = vec![vec![0; n]; n];
+        for i in 0..n {
+            for j in 0..i {
+                // The matrix is symmetric
+                let d = (sets[i].state ^ sets[j].state).count_ones();
+                distances[i][j] = d;
+                distances[j][i] = d;
+            }
+        }
+        distances
+    }
+
+    /// Returns `a` if `cond` is `true`, and `b` otherwise.
+    ///
+    /// The selection is performed with a mask derived from `cond`, without a data-dependent branch.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let a = DenseBitSet::from_integer(12);
+    /// let b = DenseBitSet::from_integer(34);
+    ///
+    /// assert_eq!(DenseBitSet::conditional_select(true, a, b), a);
+    /// assert_eq!(DenseBitSet::conditional_select(false, a, b), b);
+    /// ```
+    pub const fn conditional_select(cond: bool, a: Self, b: Self) -> Self {
+        // All ones if `cond` is true, all zeros otherwise
+        let mask = (cond as u64).wrapping_neg();
+        Self {
+            state: (a.state & mask) | (b.state & !mask),
+        }
+    }
+
+    /// Clocks the bitset as a Fibonacci linear-feedback shift register and returns the output bit.
+    ///
+    /// The feedback bit is the parity of the bits selected by `taps` (i.e. of `self & taps`).
+    /// The register is then shifted right by one: bit 0 is output, and the feedback bit is
+    /// inserted at position 63.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let mut lfsr = DenseBitSet::from_integer(0b11);
+    /// let taps = DenseBitSet::from_integer(0b10);
+    ///
+    /// assert!(lfsr.clock_lfsr(taps));
+    /// assert_eq!(lfsr.to_integer(), 0b1 | (1 << 63));
+    /// ```
+    pub fn clock_lfsr(&mut self, taps: Self) -> bool {
+        let feedback = (self.state & taps.state).count_ones() & 1;
+        let output = self.state & 1 == 1;
+        self.state = (self.state >> 1) | (u64::from(feedback) << 63);
+        output
+    }
+
+    /// Returns the 8 bytes of the bitset (little endian convention: byte 0 holds bits 0 to 7).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x0102);
+    ///
+    /// assert_eq!(bs.bytes(), [2, 1, 0, 0, 0, 0, 0, 0]);
+    /// ```
+    pub const fn bytes(self) -> [u8; 8] {
+        self.state.to_le_bytes()
+    }
+
+    /// Returns the byte at the given `index` (little endian convention: byte 0 holds bits 0 to 7).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x0102);
+    ///
+    /// assert_eq!(bs.byte(1), 1);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `index` is larger than 7.
+    pub const fn byte(self, index: usize) -> u8 {
+        assert!(
+            index < 8,
+            "This implementation is currently limited to 64 bit bitsets."
+        );
+        (self.state >> (8 * index)) as u8
+    }
+
+    /// Left shift of `n` bits, where the bits shifted beyond position 63 pile up at position 63
+    /// instead of being lost.
+    ///
+    /// In other words, the top bit of the result is set if and only if at least one set bit reaches
+    /// or goes past position 63. Unlike with `<<`, a non-empty bitset never becomes empty.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b11 << 62);
+    ///
+    /// assert_eq!(bs.saturating_shl(2).to_integer(), 1 << 63);
+    /// assert!((bs << 2).none());
+    /// ```
+    pub const fn saturating_shl(self, n: usize) -> Self {
+        if n == 0 {
+            return self;
+        }
+        // Bits at positions 63 - n and above end up at (or beyond) position 63
+        let overflow = if n >= 64 {
+            self.state != 0
+        } else {
+            self.state >> (63 - n) != 0
+        };
+        let shifted = if n >= 64 { 0 } else { self.state << n };
+        Self {
+            state: shifted | ((overflow as u64) << 63),
+        }
+    }
+
+    /// Returns the number of transitions (from 0 to 1 or from 1 to 0) between neighbouring bits
+    /// among the first `width` bits.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b1010);
+    ///
+    /// assert_eq!(bs.count_transitions(4), 3);
+    /// assert_eq!(bs.count_transitions(64), 4);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `width` is beyond the 64 bit limit.
+    pub const fn count_transitions(self, width: usize) -> u32 {
+        assert!(
+            width <= 64,
+            "This implementation is currently limited to 64 bit bitsets."
+        );
+        if width < 2 {
+            return 0;
+        }
+        // Bit i of `diff` is set if bits i and i + 1 differ
+        let diff = self.state ^ (self.state >> 1);
+        (diff & ((1 << (width - 1)) - 1)).count_ones()
+    }
+
+    /// Returns the parities of the 8 bytes of the bitset: bit `i` of the result is set if and only
+    /// if byte `i` has an odd number of set bits.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x0300_0107);
+    ///
+    /// assert_eq!(bs.byte_parities(), 0b0011);
+    /// ```
+    pub const fn byte_parities(self) -> u8 {
+        // Fold each byte onto its lowest bit
+        let mut v = self.state;
+        v ^= v >> 4;
+        v ^= v >> 2;
+        v ^= v >> 1;
+        v &= 0x0101010101010101;
+
+        // Gather the lowest bit of byte i into bit 56 + i
+        (v.wrapping_mul(0x0102040810204080) >> 56) as u8
+    }
+
+    /// Reinterprets the bitset as the bit pattern of an IEEE-754 double precision float.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x3ff0000000000000);
+    ///
+    /// assert_eq!(bs.to_f64(), 1.0);
+    /// ```
+    pub fn to_f64(self) -> f64 {
+        f64::from_bits(self.state)
+    }
+
+    /// Generates a bitset from the bit pattern of an IEEE-754 double precision float.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_f64(-0.0);
+    ///
+    /// assert_eq!(bs.to_integer(), 1 << 63);
+    /// ```
+    pub fn from_f64(f: f64) -> Self {
+        Self { state: f.to_bits() }
+    }
+
+    /// Returns the 16 nibbles (4-bit lanes) of the bitset, lowest nibble first.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x1a);
+    /// let nibbles = bs.nibbles();
+    ///
+    /// assert_eq!(nibbles[0], 0xa);
+    /// assert_eq!(nibbles[1], 0x1);
+    /// ```
+    pub const fn nibbles(self) -> [u8; 16] {
+        let mut nibbles = [0; 16];
+        let mut i = 0;
+        while i < 16 {
+            nibbles[i] = ((self.state >> (4 * i)) & 0xf) as u8;
+            i += 1;
+        }
+        nibbles
+    }
+
+    /// Generates a bitset from 16 nibbles (4-bit lanes), lowest nibble first.
+    ///
+    /// Only the 4 lowest bits of each value are used.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let mut nibbles = [0; 16];
+    /// nibbles[0] = 0xa;
+    /// nibbles[1] = 0x1;
+    ///
+    /// assert_eq!(DenseBitSet::from_nibbles(nibbles).to_integer(), 0x1a);
+    /// ```
+    pub const fn from_nibbles(nibbles: [u8; 16]) -> Self {
+        let mut state = 0;
+        let mut i = 0;
+        while i < 16 {
+            state |= ((nibbles[i] & 0xf) as u64) << (4 * i);
+            i += 1;
+        }
+        Self { state }
+    }
+
+    /// Returns the number of leading zeros of the bitset seen as a `width`-bit field, that is
+    /// the number of unset bits below position `width` and above the highest set bit.
+    ///
+    /// Bits at positions `width` and above are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b0010);
+    ///
+    /// assert_eq!(bs.leading_zeros_in(4), 2);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `width` is beyond the 64 bit limit.
+    pub const fn leading_zeros_in(self, width: usize) -> u32 {
+        let field = self.low_bits(width);
+        if field == 0 {
+            width as u32
+        } else {
+            width as u32 - (64 - field.leading_zeros())
+        }
+    }
+
+    /// Returns the number of trailing zeros of the bitset seen as a `width`-bit field, that is
+    /// the number of unset bits below the lowest set bit (at most `width`).
+    ///
+    /// Bits at positions `width` and above are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b10000);
+    ///
+    /// assert_eq!(bs.trailing_zeros_in(8), 4);
+    /// assert_eq!(bs.trailing_zeros_in(3), 3);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `width` is beyond the 64 bit limit.
+    pub const fn trailing_zeros_in(self, width: usize) -> u32 {
+        let field = self.low_bits(width);
+        if field == 0 {
+            width as u32
+        } else {
+            field.trailing_zeros()
+        }
+    }
+
+    /// Returns the `width` lowest bits of the bitset as an integer.
+    const fn low_bits(self, width: usize) -> u64 {
+        assert!(
+            width <= 64,
+            "This implementation is currently limited to 64 bit bitsets."
+        );
+        if width < 64 {
+            self.state & ((1 << width) - 1)
+        } else {
+            // This special branch is to avoid overflowing when masking
+            self.state
+        }
+    }
+
+    /// Returns `true` if the set bits form a contiguous run starting at position 0
+    /// (e.g. `0b0111`). The empty bitset is considered to be a mask of width zero.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// assert!(DenseBitSet::from_integer(0b1111).is_low_mask());
+    /// assert!(!DenseBitSet::from_integer(0b1011).is_low_mask());
+    /// ```
+    pub const fn is_low_mask(self) -> bool {
+        // Adding one to a low mask carries through all of its bits
+        self.state & self.state.wrapping_add(1) == 0
+    }
+
+    /// Returns the width of the bitset if it is a low mask (see `is_low_mask`), and `None` otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// assert_eq!(DenseBitSet::from_integer(0b1111).mask_width(), Some(4));
+    /// assert_eq!(DenseBitSet::from_integer(0b1011).mask_width(), None);
+    /// ```
+    pub const fn mask_width(self) -> Option<usize> {
+        if self.is_low_mask() {
+            Some(self.state.count_ones() as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the base 2 logarithm of the bitset seen as an integer, rounded down
+    /// (i.e. the position of the highest set bit), or `None` if no bit is set.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// assert_eq!(DenseBitSet::from_integer(1000).ilog2_floor(), Some(9));
+    /// assert_eq!(DenseBitSet::new().ilog2_floor(), None);
+    /// ```
+    pub const fn ilog2_floor(self) -> Option<usize> {
+        if self.state == 0 {
+            None
+        } else {
+            Some(63 - self.state.leading_zeros() as usize)
+        }
+    }
+
+    /// Returns the base 2 logarithm of the bitset seen as an integer, rounded up,
+    /// or `None` if no bit is set.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// assert_eq!(DenseBitSet::from_integer(1000).ilog2_ceil(), Some(10));
+    /// assert_eq!(DenseBitSet::from_integer(1024).ilog2_ceil(), Some(10));
+    /// ```
+    pub const fn ilog2_ceil(self) -> Option<usize> {
+        match self.ilog2_floor() {
+            // Powers of two have a single set bit
+            Some(l) if self.state.count_ones() > 1 => Some(l + 1),
+            l => l,
+        }
+    }
+
+    /// Swaps the high and low 32-bit halves of the bitset.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x0011223344556677);
+    ///
+    /// assert_eq!(bs.swap_halves().to_integer(), 0x4455667700112233);
+    /// ```
+    pub const fn swap_halves(self) -> Self {
+        Self {
+            state: self.state.rotate_left(32),
+        }
+    }
+
+    /// Swaps the two 16-bit lanes within each 32-bit half of the bitset.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x0011223344556677);
+    ///
+    /// assert_eq!(bs.swap_u16_lanes().to_integer(), 0x2233001166774455);
+    /// ```
+    pub const fn swap_u16_lanes(self) -> Self {
+        let v = self.state;
+        Self {
+            state: ((v >> 16) & 0x0000FFFF0000FFFF) | ((v & 0x0000FFFF0000FFFF) << 16),
+        }
+    }
+
+    /// Swaps the two bytes within each 16-bit lane of the bitset.
+    ///
+    /// Applying `swap_halves`, `swap_u16_lanes` and `swap_u8_lanes` reverses the byte order.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x0011223344556677);
+    ///
+    /// assert_eq!(bs.swap_u8_lanes().to_integer(), 0x1100332255447766);
+    /// ```
+    pub const fn swap_u8_lanes(self) -> Self {
+        let v = self.state;
+        Self {
+            state: ((v >> 8) & 0x00FF00FF00FF00FF) | ((v & 0x00FF00FF00FF00FF) << 8),
+        }
+    }
+
+    /// Returns a bitset containing only the lowest set bit (empty if no bit is set).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b101100);
+    ///
+    /// assert_eq!(bs.isolate_lowest_set().to_integer(), 0b100);
+    /// ```
+    pub const fn isolate_lowest_set(self) -> Self {
+        Self {
+            state: self.state & self.state.wrapping_neg(),
+        }
+    }
+
+    /// Returns a bitset containing only the `n`-th set bit (starting from zero), or an empty bitset
+    /// if fewer than `n + 1` bits are set.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b101100);
+    ///
+    /// assert_eq!(bs.isolate_nth_set(1).to_integer(), 0b1000);
+    /// assert!(bs.isolate_nth_set(3).none());
+    /// ```
+    pub const fn isolate_nth_set(self, n: usize) -> Self {
+        match self.nth_set_bit(n) {
+            Some(p) => Self { state: 1 << p },
+            None => Self { state: 0 },
+        }
+    }
+
+    /// Returns the Hamming weight of each of the 8 bytes of the bitset (little endian convention:
+    /// element 0 is the weight of bits 0 to 7).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x0f01ff);
+    ///
+    /// assert_eq!(bs.byte_popcounts(), [8, 1, 4, 0, 0, 0, 0, 0]);
+    /// ```
+    pub const fn byte_popcounts(self) -> [u8; 8] {
+        // SWAR popcount, stopping before the per-byte sums are folded together
+        let mut v = self.state;
+        v -= (v >> 1) & 0x5555555555555555;
+        v = (v & 0x3333333333333333) + ((v >> 2) & 0x3333333333333333);
+        v = (v + (v >> 4)) & 0x0F0F0F0F0F0F0F0F;
+        v.to_le_bytes()
+    }
+
+    /// Returns a bitset holding the `bits` lowest bits of the bitset in reverse order (the other bits
+    /// are set to `false`).
+    ///
+    /// Unlike `reverse`, the reversed bits remain in the low positions, which is what a bit-reversal
+    /// permutation of a `bits`-bit index requires.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0b110001);
+    ///
+    /// assert_eq!(bs.bit_reverse_low(3).to_integer(), 0b100);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `bits` is beyond the 64 bit limit.
+    pub const fn bit_reverse_low(self, bits: usize) -> Self {
+        assert!(
+            bits <= 64,
+            "This implementation is currently limited to 64 bit bitsets."
+        );
+        if bits == 0 {
+            return Self { state: 0 };
+        }
+        Self {
+            state: self.reverse().state >> (64 - bits),
+        }
+    }
+
+    /// Parses a bitset from a string, detecting the base from its prefix: `0b` for binary, `0o` for
+    /// octal, `0x` for hexadecimal, and no prefix for decimal.
+    ///
+    /// As for Rust integer literals, prefixes are lowercase only (`0X2a` is rejected), and no sign
+    /// is accepted, neither before nor after the prefix.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// assert_eq!(DenseBitSet::parse_auto("0b101010").unwrap().to_integer(), 42);
+    /// assert_eq!(DenseBitSet::parse_auto("0x2a").unwrap().to_integer(), 42);
+    /// assert_eq!(DenseBitSet::parse_auto("42").unwrap().to_integer(), 42);
+    /// assert!(DenseBitSet::parse_auto("0xzz").is_err());
+    /// assert!(DenseBitSet::parse_auto("+42").is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns an error if the string is empty, contains invalid characters for the
+    /// detected base, or represents a value that does not fit in 64 bits.
+    pub fn parse_auto(s: &str) -> Result<Self, ParseIntError> {
+        let (digits, base) = if let Some(d) = s.strip_prefix("0b") {
+            (d, 2)
+        } else if let Some(d) = s.strip_prefix("0o") {
+            (d, 8)
+        } else if let Some(d) = s.strip_prefix("0x") {
+            (d, 16)
+        } else {
+            (s, 10)
+        };
+        if digits.starts_with(['+', '-']) {
+            // `from_str_radix` would accept a leading `+`, so signs are rejected beforehand.
+            // `ParseIntError` cannot be built outside of the standard library: the `InvalidDigit`
+            // error is the one returned for a lone sign.
+            return Err(u64::from_str_radix("+", base).unwrap_err());
+        }
+        let state = u64::from_str_radix(digits, base)?;
+        Ok(Self { state })
+    }
+
+    /// Returns a copy of the bitset rotated left by `shift` bits.
+    ///
+    /// Unlike `rotl`, this does not mutate the bitset, and `shift` is reduced modulo 64.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let dbs = DenseBitSet::from_integer(1 << 63);
+    /// assert_eq!(dbs.wrapping_rotl(1).to_integer(), 1);
+    /// assert_eq!(dbs.wrapping_rotl(65).to_integer(), 1);
+    /// ```
+    pub const fn wrapping_rotl(self, shift: usize) -> Self {
+        Self {
+            state: self.state.rotate_left((shift % 64) as u32),
+        }
+    }
+
+    /// Returns a copy of the bitset rotated right by `shift` bits.
+    ///
+    /// Unlike `rotr`, this does not mutate the bitset, and `shift` is reduced modulo 64.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let dbs = DenseBitSet::from_integer(1);
+    /// assert_eq!(dbs.wrapping_rotr(1).to_integer(), 1 << 63);
+    /// assert_eq!(dbs.wrapping_rotr(129).to_integer(), 1 << 63);
+    /// ```
+    pub const fn wrapping_rotr(self, shift: usize) -> Self {
+        Self {
+            state: self.state.rotate_right((shift % 64) as u32),
+        }
+    }
+
+    /// Returns a lazy iterator over the positions of the set bits, in ascending order.
+    ///
+    /// The iterator knows its length, which is the Hamming weight of the bitset.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let dbs = DenseBitSet::from_integer(0b101001);
+    /// assert_eq!(dbs.iter_ones().len(), 3);
+    /// assert_eq!(dbs.iter_ones().collect::<Vec<_>>(), vec![0, 3, 5]);
+    /// ```
+    pub fn iter_ones(&self) -> impl ExactSizeIterator<Item = usize> {
+        IterOnes { state: self.state }
+    }
+
+    /// Ternary match: returns `true` if the bitset agrees with `pattern` on every bit set in
+    /// `care_mask`. Bits outside `care_mask` are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let dbs = DenseBitSet::from_integer(0b1011);
+    /// let pattern = DenseBitSet::from_integer(0b0011);
+    ///
+    /// assert!(dbs.matches(pattern, DenseBitSet::from_integer(0b0111)));
+    /// assert!(!dbs.matches(pattern, DenseBitSet::from_integer(0b1111)));
+    /// ```
+    pub const fn matches(self, pattern: Self, care_mask: Self) -> bool {
+        (self.state ^ pattern.state) & care_mask.state == 0
+    }
+
+    /// Returns the number of bitsets in `sets` which match `pattern` on the bits set in
+    /// `care_mask` (see `matches`).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let sets = [
+    ///     DenseBitSet::from_integer(0b1001),
+    ///     DenseBitSet::from_integer(0b0001),
+    ///     DenseBitSet::from_integer(0b0011),
+    /// ];
+    /// let pattern = DenseBitSet::from_integer(0b0001);
+    ///
+    /// assert_eq!(DenseBitSet::count_matching(&sets, pattern, DenseBitSet::from_integer(0b0011)), 2);
+    /// ```
+    pub fn count_matching(sets: &[Self], pattern: Self, care_mask: Self) -> usize {
+        sets.iter()
+            .filter(|s| s.matches(pattern, care_mask))
+            .count()
+    }
+
+    /// Broadcasts each bit to a byte: element `i` of the result is `0xFF` if bit `i` is set,
+    /// and `0x00` otherwise. This is the expansion used to build byte-wise blend masks.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let lanes = DenseBitSet::from_integer(0b101).expand_to_bytes();
+    ///
+    /// assert_eq!(&lanes[..4], &[0xFF, 0x00, 0xFF, 0x00]);
+    /// assert!(lanes[4..].iter().all(|&b| b == 0));
+    /// ```
+    pub const fn expand_to_bytes(self) -> [u8; 64] {
+        let mut lanes = [0; 64];
+        let mut i = 0;
+        while i < 64 {
+            lanes[i] = (0u8).wrapping_sub(((self.state >> i) & 1) as u8);
+            i += 1;
+        }
+        lanes
+    }
+
+    /// Returns the bits of `other` where `mask` is set, and the bits of `self` elsewhere.
+    ///
+    /// This is the read-modify-write used to update the fields selected by `mask`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let reg = DenseBitSet::from_integer(0xAB_CD);
+    /// let update = DenseBitSet::from_integer(0x12_34);
+    /// let mask = DenseBitSet::from_integer(0x00_FF);
+    ///
+    /// assert_eq!(reg.merge(update, mask).to_integer(), 0xAB_34);
+    /// ```
+    pub const fn merge(self, other: Self, mask: Self) -> Self {
+        Self {
+            state: (self.state & !mask.state) | (other.state & mask.state),
+        }
+    }
+
+    /// Returns the bitset as 8 bytes in little endian order: byte 0 holds bits 0 to 7, which is
+    /// consistent with the bit positions. This is the same as `bytes`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x0102);
+    ///
+    /// assert_eq!(bs.to_le_bytes(), [2, 1, 0, 0, 0, 0, 0, 0]);
+    /// ```
+    pub const fn to_le_bytes(self) -> [u8; 8] {
+        self.state.to_le_bytes()
+    }
+
+    /// Returns the bitset as 8 bytes in big endian order: byte 0 holds bits 56 to 63.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x0102);
+    ///
+    /// assert_eq!(bs.to_be_bytes(), [0, 0, 0, 0, 0, 0, 1, 2]);
+    /// ```
+    pub const fn to_be_bytes(self) -> [u8; 8] {
+        self.state.to_be_bytes()
+    }
+
+    /// Builds a bitset from 8 bytes in little endian order: byte 0 holds bits 0 to 7.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_le_bytes([2, 1, 0, 0, 0, 0, 0, 0]);
+    ///
+    /// assert_eq!(bs.to_integer(), 0x0102);
+    /// ```
+    pub const fn from_le_bytes(bytes: [u8; 8]) -> Self {
+        Self {
+            state: u64::from_le_bytes(bytes),
+        }
+    }
+
+    /// Builds a bitset from 8 bytes in big endian order: byte 0 holds bits 56 to 63.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_be_bytes([0, 0, 0, 0, 0, 0, 1, 2]);
+    ///
+    /// assert_eq!(bs.to_integer(), 0x0102);
+    /// ```
+    pub const fn from_be_bytes(bytes: [u8; 8]) -> Self {
+        Self {
+            state: u64::from_be_bytes(bytes),
+        }
+    }
+
+    /// Returns the `N` lowest bits of the bitset as an array, where element `i` is bit `i`.
+    ///
+    /// Using `N > 64` is a compile-time error.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bits: [bool; 4] = DenseBitSet::from_integer(0b1101).to_bits();
+    ///
+    /// assert_eq!(bits, [true, false, true, true]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bits: [bool; 65] = DenseBitSet::new().to_bits();
+    /// ```
+    pub const fn to_bits<const N: usize>(self) -> [bool; N] {
+        let () = BitWidth::<N>::CHECK;
+        let mut bits = [false; N];
+        let mut i = 0;
+        while i < N {
+            bits[i] = (self.state >> i) & 1 == 1;
+            i += 1;
+        }
+        bits
+    }
+
+    /// Returns the number of bits set to `false`, i.e. `64 - get_weight()`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// assert_eq!(DenseBitSet::from_integer(0b1011).count_zeros(), 61);
+    /// ```
+    pub const fn count_zeros(self) -> u32 {
+        self.state.count_zeros()
+    }
+
+    /// Rotates the `length`-bit field starting at `position` left by `shift` bits (modulo
+    /// `length`), in place. The bits outside of the field are left untouched.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let mut bs = DenseBitSet::from_integer(0b1_1001_1);
+    /// bs.rotate_field(1, 4, 1);
+    ///
+    /// assert_eq!(bs.to_integer(), 0b1_0011_1);
     /// ```
-    pub const fn new() -> Self {
-        Self { state: 0 }
+    ///
+    /// # Panics
+    /// This function will panic if `length` is zero or if `position + length > 64`.
+    pub fn rotate_field(&mut self, position: usize, length: usize, shift: usize) {
+        let field = self.extract(position, length);
+        let shift = shift % length;
+        let rotated = if length == 64 {
+            field.rotate_left(shift as u32)
+        } else if shift == 0 {
+            field
+        } else {
+            ((field << shift) | (field >> (length - shift))) & ((1 << length) - 1)
+        };
+        self.insert(position, length, rotated);
     }
 
-    /// Generates a bitset from an integer (little endian convention).
+    /// Returns the position of the first set bit at or after `from`, if any.
     ///
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
     ///
-    /// let bs = DenseBitSet::from_integer(1234567890);
+    /// let bs = DenseBitSet::from_integer(0b100100);
+    ///
+    /// assert_eq!(bs.next_set_bit(3), Some(5));
+    /// assert_eq!(bs.next_set_bit(6), None);
     /// ```
-    pub const fn from_integer(i: u64) -> Self {
-        Self { state: i }
+    pub const fn next_set_bit(self, from: usize) -> Option<usize> {
+        if from >= 64 {
+            return None;
+        }
+        let w = self.state >> from;
+        if w == 0 {
+            None
+        } else {
+            Some(from + w.trailing_zeros() as usize)
+        }
     }
 
-    /// Generates a bitset from a string and a base (little endian convention).
-    ///
-    /// The `base` must be an integer between 2 and 32.
+    /// Returns the position of the last set bit at or before `from`, if any.
     ///
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
     ///
-    /// let mut bs1 = DenseBitSet::from_string("101010", 2);
-    /// let mut bs2 = DenseBitSet::from_string("2a", 16);
+    /// let bs = DenseBitSet::from_integer(0b100100);
     ///
-    /// assert_eq!(bs1,bs2);
+    /// assert_eq!(bs.prev_set_bit(4), Some(2));
+    /// assert_eq!(bs.prev_set_bit(1), None);
     /// ```
-    ///
-    /// # Panics
-    ///  
-    /// This function will panic if an incorrect `base` is provided or if invalid
-    /// characters are found when parsing.
-    pub fn from_string(s: &str, base: u32) -> Self {
-        assert!((2..=32).contains(&base), "Only supports base from 2 to 32");
-        let val = u64::from_str_radix(s, base);
-        let res: u64 = val.expect("Failed to parse string");
-        Self { state: res }
+    pub const fn prev_set_bit(self, from: usize) -> Option<usize> {
+        let from = if from > 63 { 63 } else { from };
+        let w = self.state << (63 - from);
+        if w == 0 {
+            None
+        } else {
+            Some(from - w.leading_zeros() as usize)
+        }
     }
 
-    /// Returns an integer representing the bitset (little endian convention).
+    /// Returns the number of bits on which the bitset agrees with `other`, and the number of bits
+    /// on which they differ (the Hamming distance), with a single popcount.
     ///
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
     ///
-    /// let bs = DenseBitSet::from_integer(1234);
+    /// let a = DenseBitSet::from_integer(0b1100);
+    /// let b = DenseBitSet::from_integer(0b1010);
     ///
-    /// assert_eq!(bs.to_integer(), 1234);
+    /// assert_eq!(a.agreement(b), (62, 2));
     /// ```
-    pub const fn to_integer(self) -> u64 {
-        self.state
+    pub const fn agreement(self, other: Self) -> (u32, u32) {
+        let hamming = (self.state ^ other.state).count_ones();
+        (64 - hamming, hamming)
     }
 
-    /// Returns an integer representation of the bitset starting at the given `position` with given `length` (little endian convention).
+    /// Interleaves the bits of `n = channels.len()` bitsets: bit `k` of the result is bit `k / n`
+    /// of `channels[k % n]`.
     ///
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
     ///
-    /// let bs = DenseBitSet::from_integer(0b11110101010010);
-    /// let value = bs.extract(5,6);
+    /// let r = DenseBitSet::from_integer(0b11);
+    /// let g = DenseBitSet::from_integer(0b00);
+    /// let b = DenseBitSet::from_integer(0b01);
     ///
-    /// assert_eq!(value, 42);
+    /// assert_eq!(DenseBitSet::interleave_n(&[r, g, b]).to_integer(), 0b001_101);
     /// ```
     ///
     /// # Panics
-    /// This function will panic if `length` is zero or if one tries to
-    /// access a bit beyond the 64 bit limit (i.e., `position + length > 64`).
-    pub const fn extract(self, position: usize, length: usize) -> u64 {
-        assert!(
-            position + length <= 64,
-            "This implementation is currently limited to 64 bit bitsets."
-        );
-        assert!(length > 0, "Cannot extract a zero-width slice.");
-        if length < 64 {
-            (self.state >> position) & ((1 << length) - 1)
-        } else {
-            // This special branch is to avoid overflowing when masking
-            self.state >> position
+    /// This function will panic if a set bit of a channel would land beyond the 64 bit limit,
+    /// i.e. if bit `j` of `channels[c]` is set with `j * n + c >= 64`.
+    pub fn interleave_n(channels: &[Self]) -> Self {
+        let n = channels.len();
+        let mut state = 0;
+        for (c, channel) in channels.iter().enumerate() {
+            let mut v = channel.state;
+            while v != 0 {
+                let j = v.trailing_zeros() as usize;
+                assert!(
+                    j * n + c < 64,
+                    "This implementation is currently limited to 64 bit bitsets."
+                );
+                state |= 1 << (j * n + c);
+                // Clear the lowest set bit
+                v &= v - 1;
+            }
         }
+        Self { state }
     }
 
-    /// Returns nothing, mutates the `DenseBitSet` to insert `value` at the given `position`.
-    ///
-    /// Note that `value` is treated as a `length`-bit integer (little endian convention);
-    /// if necessary, `value` is padded with zeros (or truncated) to be of the correct length.
+    /// Splits the bitset into `n` channels, the inverse of `interleave_n`: bit `j` of channel `c`
+    /// is bit `j * n + c` of the bitset.
     ///
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
     ///
-    /// let mut bs = DenseBitSet::new();
-    /// bs.insert(10, 8, 0b10101011);
-    /// bs.insert(3,1,1);
+    /// let channels = DenseBitSet::from_integer(0b001_101).deinterleave_n(3);
     ///
-    /// assert_eq!(bs.to_integer(), 0b101010110000001000)
+    /// assert_eq!(channels[0].to_integer(), 0b11);
+    /// assert_eq!(channels[1].to_integer(), 0b00);
+    /// assert_eq!(channels[2].to_integer(), 0b01);
     /// ```
     ///
     /// # Panics
-    /// This function will panic if `length` is zero, or if one tries to
-    /// insert a bit beyond the 64 bit limit (i.e. `position + length > 64`)
-    pub fn insert(&mut self, position: usize, length: usize, value: u64) {
-        assert!(
-            position + length <= 64,
-            "This implementation is currently limited to 64 bit bitsets."
-        );
-        assert!(length > 0, "Cannot insert zero-width slice");
-        if length < 64 {
-            let mut u = u64::max_value();
-            u ^= ((1 << length) - 1) << position;
-            self.state &= u;
-            self.state |= value << position;
-        } else {
-            self.state = value;
+    /// This function will panic if `n` is zero.
+    pub fn deinterleave_n(self, n: usize) -> Vec<Self> {
+        assert!(n > 0, "Cannot split into zero channels.");
+        let mut channels = vec![Self::new(); n];
+        let mut v = self.state;
+        while v != 0 {
+            let k = v.trailing_zeros() as usize;
+            channels[k % n].state |= 1 << (k / n);
+            // Clear the lowest set bit
+            v &= v - 1;
         }
+        channels
     }
 
-    /// Returns `true` if and only if all bits are set to `true`.
+    /// Returns the set difference of the two bitsets: the bits set in `self` but not in `other`,
+    /// i.e. `self & !other`.
     ///
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
-    /// use rust_dense_bitset::BitSet;
     ///
-    /// let mut bs = DenseBitSet::from_integer(u64::max_value());
+    /// let a = DenseBitSet::from_integer(0b1100);
+    /// let b = DenseBitSet::from_integer(0b1010);
     ///
-    /// assert!(bs.all());
+    /// assert_eq!(a.difference(b).to_integer(), 0b0100);
+    /// ```
+    pub const fn difference(self, other: Self) -> Self {
+        Self {
+            state: self.state & !other.state,
+        }
+    }
+
+    /// Returns the symmetric difference of the two bitsets: the bits set in exactly one of them,
+    /// i.e. `self ^ other`.
     ///
-    /// bs.set_bit(28,false);
-    /// bs.all(); // -> false
+    /// # Example
     /// ```
-    pub const fn all(self) -> bool {
-        self.state == u64::max_value()
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let a = DenseBitSet::from_integer(0b1100);
+    /// let b = DenseBitSet::from_integer(0b1010);
+    ///
+    /// assert_eq!(a.symmetric_difference(b).to_integer(), 0b0110);
+    /// ```
+    pub const fn symmetric_difference(self, other: Self) -> Self {
+        Self {
+            state: self.state ^ other.state,
+        }
     }
 
-    /// Returns `true` if at least one of the bits is set to `true`.
+    /// Returns the Hamming weights of the bits at even positions and at odd positions.
     ///
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
-    /// use rust_dense_bitset::BitSet;
     ///
-    /// let mut bs = DenseBitSet::from_integer(2048);
+    /// let bs = DenseBitSet::from_integer(0b11101);
     ///
-    /// assert!(bs.any());
+    /// assert_eq!(bs.popcount_even_odd(), (3, 1));
+    /// ```
+    pub const fn popcount_even_odd(self) -> (u32, u32) {
+        (
+            (self.state & 0x5555_5555_5555_5555).count_ones(),
+            (self.state & 0xAAAA_AAAA_AAAA_AAAA).count_ones(),
+        )
+    }
+
+    /// Returns `true` if every bit set in `self` is also set in `other`.
     ///
-    /// bs.set_bit(11,false);
-    /// bs.any(); // -> false
+    /// # Example
     /// ```
-    pub const fn any(self) -> bool {
-        self.state > 0
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let a = DenseBitSet::from_integer(0b0100);
+    /// let b = DenseBitSet::from_integer(0b1100);
+    ///
+    /// assert!(a.is_subset(b));
+    /// assert!(!b.is_subset(a));
+    /// ```
+    pub const fn is_subset(self, other: Self) -> bool {
+        self.state & !other.state == 0
     }
 
-    /// Returns `true` if all the bits are set to `false`.
+    /// Returns `true` if every bit set in `other` is also set in `self`.
     ///
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
-    /// use rust_dense_bitset::BitSet;
     ///
-    /// let mut bs = DenseBitSet::from_integer(2048);
-    /// bs.set_bit(11,false);
+    /// let a = DenseBitSet::from_integer(0b0100);
+    /// let b = DenseBitSet::from_integer(0b1100);
     ///
-    /// assert!(bs.none());
+    /// assert!(b.is_superset(a));
+    /// assert!(!a.is_superset(b));
     /// ```
-    pub const fn none(self) -> bool {
-        !self.any()
+    pub const fn is_superset(self, other: Self) -> bool {
+        other.is_subset(self)
     }
 
-    /// Returns a bit-reversed `DenseBitSet`.
+    /// Returns `true` if the two bitsets have no set bit in common.
     ///
-    /// This method is using a constant time bit reversal algorithm for 64 bits integers.
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let a = DenseBitSet::from_integer(0b0101);
+    ///
+    /// assert!(a.is_disjoint(DenseBitSet::from_integer(0b1010)));
+    /// assert!(!a.is_disjoint(DenseBitSet::from_integer(0b0110)));
+    /// ```
+    pub const fn is_disjoint(self, other: Self) -> bool {
+        self.state & other.state == 0
+    }
+
+    /// Swaps the two bits of each pair: bit `2i` and bit `2i + 1` are exchanged.
     ///
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
     ///
-    /// let bs = DenseBitSet::from_integer(0b11110001);
-    /// let bs2 = bs.reverse();
+    /// let bs = DenseBitSet::from_integer(0b01_10_11_00);
     ///
-    /// assert_eq!(bs2.to_integer(), 0b1000111100000000000000000000000000000000000000000000000000000000);
+    /// assert_eq!(bs.swap_bit_pairs().to_integer(), 0b10_01_11_00);
     /// ```
-    pub const fn reverse(self) -> Self {
-        let mut v = self.state;
-        v = ((v >> 1) & 0x5555555555555555_u64) | ((v & 0x5555555555555555_u64) << 1);
+    pub const fn swap_bit_pairs(self) -> Self {
+        let v = self.state;
+        Self {
+            state: ((v >> 1) & 0x5555555555555555_u64) | ((v & 0x5555555555555555_u64) << 1),
+        }
+    }
+
+    /// Reverses the order of the 4 bits inside each nibble, keeping the order of the nibbles.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_integer(0x1_3);
+    ///
+    /// assert_eq!(bs.reverse_within_nibbles().to_integer(), 0x8_c);
+    /// ```
+    pub const fn reverse_within_nibbles(self) -> Self {
+        let mut v = self.swap_bit_pairs().state;
         v = ((v >> 2) & 0x3333333333333333_u64) | ((v & 0x3333333333333333_u64) << 2);
-        v = ((v >> 4) & 0x0F0F0F0F0F0F0F0F_u64) | ((v & 0x0F0F0F0F0F0F0F0F_u64) << 4);
+        Self { state: v }
+    }
+
+    /// Returns the bitset reinterpreted as a signed integer (two's complement): the bit pattern
+    /// is preserved, and bit 63 is the sign bit.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// assert_eq!(DenseBitSet::from_integer(u64::MAX).to_i64(), -1);
+    /// assert_eq!(DenseBitSet::from_integer(42).to_i64(), 42);
+    /// ```
+    pub const fn to_i64(self) -> i64 {
+        self.state as i64
+    }
 
+    /// Builds a bitset from the two's complement bit pattern of a signed integer.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let bs = DenseBitSet::from_i64(-2);
+    ///
+    /// assert_eq!(bs.to_integer(), 0xFFFF_FFFF_FFFF_FFFE);
+    /// ```
+    pub const fn from_i64(value: i64) -> Self {
         Self {
-            state: v.swap_bytes(),
+            state: value as u64,
         }
     }
 
-    /// Right rotation of `shift` bits.
+    /// Sets the bits at positions `start..end` to `value`, in place.
     ///
-    /// Shifts the bits to the right, wrapping the truncated bits to the end of the bitset.
+    /// # Example
+    /// ```
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let mut bs = DenseBitSet::from_integer(0b1000_0001);
+    /// bs.set_range(2, 5, true);
+    ///
+    /// assert_eq!(bs.to_integer(), 0b1001_1101);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `start > end` or if `end > 64`.
+    pub fn set_range(&mut self, start: usize, end: usize, value: bool) {
+        let mask = Self::range_mask(start, end).state;
+        if value {
+            self.state |= mask;
+        } else {
+            self.state &= !mask;
+        }
+    }
+
+    /// Returns a bitset with exactly the bits at positions `start..end` set.
     ///
-    /// The rotation is done in-place, so the bitset needs to be mutable.
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
     ///
-    /// let mut bs = DenseBitSet::from_integer(0b111000111000111000111);
-    /// bs.rotr(10);
+    /// assert_eq!(DenseBitSet::range_mask(4, 8).to_integer(), 0b1111_0000);
+    /// assert_eq!(DenseBitSet::range_mask(0, 64), DenseBitSet::FULL);
+    /// ```
     ///
-    /// assert_eq!(bs.to_integer(), 0b111000111000000000000000000000000000000000000000000011100011100 );
+    /// # Panics
+    /// This function will panic if `start > end` or if `end > 64`.
+    pub const fn range_mask(start: usize, end: usize) -> Self {
+        assert!(
+            end <= 64,
+            "This implementation is currently limited to 64 bit bitsets."
+        );
+        assert!(start <= end, "The start of a range cannot exceed its end.");
+        if start == end {
+            return Self::EMPTY;
+        }
+        Self {
+            state: Self::FULL.low_bits(end - start) << start,
+        }
+    }
+
+    /// Returns a bitset with exactly the bits at positions `start..=end` set.
+    ///
+    /// # Example
     /// ```
-    pub fn rotr(&mut self, shift: u32) {
-        self.state = self.state.rotate_right(shift);
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// assert_eq!(DenseBitSet::range_mask_inclusive(4, 7).to_integer(), 0b1111_0000);
+    /// assert_eq!(DenseBitSet::range_mask_inclusive(63, 63).to_integer(), 1 << 63);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `start > end` or if `end > 63`.
+    pub const fn range_mask_inclusive(start: usize, end: usize) -> Self {
+        assert!(
+            end < 64,
+            "This implementation is currently limited to 64 bit bitsets."
+        );
+        Self::range_mask(start, end + 1)
     }
 
-    /// Left rotation of `shift` bits.
+    /// Toggles the bits at positions `start..end`, in place.
     ///
-    /// Shifts the bits to the left, wrapping the truncated bits to the beginning of the bitset.
-    ///  
-    /// The rotation is done in place, so the bitset needs to be mutable.
     /// # Example
     /// ```
     /// use rust_dense_bitset::DenseBitSet;
     ///
-    /// let mut bs = DenseBitSet::from_integer(0b111000111000111000111);
-    /// bs.rotl(10);
+    /// let mut bs = DenseBitSet::from_integer(0b1010_1010);
+    /// bs.flip_range(2, 6);
     ///
-    /// assert_eq!(bs.to_integer(), 0b1110001110001110001110000000000 );
+    /// assert_eq!(bs.to_integer(), 0b1001_0110);
     /// ```
-    pub fn rotl(&mut self, shift: u32) {
-        self.state = self.state.rotate_left(shift);
+    ///
+    /// # Panics
+    /// This function will panic if `start > end` or if `end > 64`.
+    pub fn flip_range(&mut self, start: usize, end: usize) {
+        self.state ^= Self::range_mask(start, end).state;
     }
 
-    /// Returns the position of the first set bit (little endian convention)
+    /// Inverts every bit of the bitset, in place.
     ///
     /// # Example
     /// ```
-    /// # use rust_dense_bitset::DenseBitSet;
-    /// let dbs = DenseBitSet::from_integer(256);
-    /// println!("{}", dbs.first_set());
+    /// use rust_dense_bitset::DenseBitSet;
+    ///
+    /// let mut bs = DenseBitSet::from_integer(0b1010);
+    /// bs.flip();
+    ///
+    /// assert_eq!(bs.to_integer(), !0b1010);
     /// ```
-    pub const fn first_set(self) -> u32 {
-        self.state.trailing_zeros()
+    pub fn flip(&mut self) {
+        self.state = !self.state
     }
 }
 
@@ -366,7 +1940,7 @@ impl BitSet for DenseBitSet {
     /// println!("{}", bs.to_string()) // -> "0000000000000000000000000001000000000000000000000001000100000000"
     /// ```
     fn to_string(self) -> String {
-        format!("{:064b}", self.state)
+        format!("{}", self)
     }
 }
 
@@ -376,6 +1950,36 @@ impl fmt::Debug for DenseBitSet {
     }
 }
 
+/// Writes the bitset as a 64 character binary string (little endian, with leading zeroes),
+/// like `to_string`.
+impl fmt::Display for DenseBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:064b}", self.state)
+    }
+}
+
+/// Writes the bitset as a hexadecimal integer, honoring the usual formatting flags.
+impl fmt::LowerHex for DenseBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.state, f)
+    }
+}
+
+/// Writes the bitset as a hexadecimal integer, honoring the usual formatting flags.
+impl fmt::UpperHex for DenseBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.state, f)
+    }
+}
+
+/// Writes the bitset as a binary integer without leading zeros, honoring the usual formatting
+/// flags (e.g. `{:#b}` or `{:064b}`).
+impl fmt::Binary for DenseBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Binary::fmt(&self.state, f)
+    }
+}
+
 impl PartialEq for DenseBitSet {
     fn eq(&self, other: &Self) -> bool {
         self.state == other.to_integer()
@@ -487,3 +2091,28 @@ impl ShrAssign<usize> for DenseBitSet {
         }
     }
 }
+
+/// Iterator over the positions of the set bits of a `DenseBitSet`, in ascending order.
+struct IterOnes {
+    state: u64,
+}
+
+impl Iterator for IterOnes {
+    type Item = usize;
+    fn next(&mut self) -> Option<usize> {
+        if self.state == 0 {
+            return None;
+        }
+        let position = self.state.trailing_zeros() as usize;
+        // Clear the lowest set bit
+        self.state &= self.state - 1;
+        Some(position)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.state.count_ones() as usize;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for IterOnes {}
@@ -4,6 +4,10 @@ use crate::u64impl::DenseBitSet;
 use std::cmp::{max, min};
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::io::{self, Read, Write};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 /// Overload of &, &=, |, |=, ^, ^=, !, <<, <<=, >>, >>=
 use std::ops::{
@@ -17,11 +21,30 @@ use std::ops::{
 ///
 /// This structure implements `BitSet, Clone, Default, Debug, Hash, PartialEq, Eq` and bit operations.
 #[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DenseBitSetExtended {
     state: Vec<u64>,
     size: usize,
 }
 
+/// Indicates which of two compared bitsets has a given bit set (see `DenseBitSetExtended::changed_positions`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The bit is set in the bitset on which the method is called, but not in the other one
+    Left,
+    /// The bit is set in the other bitset, but not in the one on which the method is called
+    Right,
+}
+
+/// Iterator over the bits of a `DenseBitSetExtended`, from position 0 up to its size (excluded).
+///
+/// This is created by iterating over a `&DenseBitSetExtended`.
+#[derive(Clone, Debug)]
+pub struct Bits<'a> {
+    bitset: &'a DenseBitSetExtended,
+    position: usize,
+}
+
 impl DenseBitSetExtended {
     /// Returns a new empty `DenseBitsetExtended`
     ///    
@@ -54,6 +77,49 @@ impl DenseBitSetExtended {
         Self { state, size: 0 }
     }
 
+    /// Returns an empty `DenseBitsetExtended` with pre-allocated memory for at least `words` 64-bit
+    /// words.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSetExtended};
+    /// let mut bs = DenseBitSetExtended::with_capacity_words(2);
+    /// assert!(bs.capacity_words() >= 2);
+    /// bs.set_bit(127, true); // No additional allocation performed
+    /// ```
+    pub fn with_capacity_words(words: usize) -> Self {
+        let state: Vec<u64> = Vec::with_capacity(words);
+        Self { state, size: 0 }
+    }
+
+    /// Reserves memory for `words` more 64-bit words than currently stored, without deliberately
+    /// over-allocating (as with `Vec::reserve_exact`, the allocator may still provide more).
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let mut bs = DenseBitSetExtended::new();
+    /// bs.reserve_exact_words(3);
+    /// assert!(bs.capacity_words() >= 3);
+    /// ```
+    pub fn reserve_exact_words(&mut self, words: usize) {
+        self.state.reserve_exact(words);
+    }
+
+    /// Returns the number of 64-bit words the bitset can hold without reallocating.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSetExtended};
+    /// let mut bs = DenseBitSetExtended::with_capacity_words(4);
+    /// let capacity = bs.capacity_words();
+    /// bs.set_bit(200, true);
+    /// assert_eq!(bs.capacity_words(), capacity); // 4 words hold 256 bits
+    /// ```
+    pub fn capacity_words(&self) -> usize {
+        self.state.capacity()
+    }
+
     /// Returns a `DenseBitSetExtended` extending a given `DenseBitSet`.
     ///
     /// # Example
@@ -71,30 +137,24 @@ impl DenseBitSetExtended {
 
     /// Returns `true` if and only if all bits are set to `true`
     pub fn all(&self) -> bool {
-        let l = self.state.len();
-        for i in 0..l - 1 {
-            if self.state[i] != u64::max_value() {
-                return false;
-            }
-        }
-        if self.size % 64 == 0 {
-            if self.state[l - 1] != u64::max_value() {
+        let l = self.num_words();
+        for i in 0..l {
+            let expected = if i + 1 == l && self.size & 63 != 0 {
+                (1 << (self.size % 64)) - 1
+            } else {
+                u64::MAX
+            };
+            if self.masked_word(i) != expected {
                 return false;
             }
-        } else if self.state[l - 1] != ((1 << (self.size % 64)) - 1) {
-            return false;
         }
         true
     }
 
     /// Returns `true` if at least one bit is set to `true`
     pub fn any(&self) -> bool {
-        for &s in &self.state {
-            if s > 0 {
-                return true;
-            }
-        }
-        false
+        // Note: bits beyond the size, if any, are ignored
+        (0..self.num_words()).any(|i| self.masked_word(i) != 0)
     }
 
     /// Returns `true` if all the bits are set to `false`
@@ -149,26 +209,21 @@ impl DenseBitSetExtended {
 
         match actual_length + offset {
             // Remain within the boundary of an element
-            l if l < 64 => (self.get(idx) >> offset) & ((1 << actual_length) - 1),
+            l if l < 64 => (self.get_word(idx) >> offset) & ((1 << actual_length) - 1),
 
             // Special case to avoid masking overflow
-            64 => self.get(idx) >> offset,
+            64 => self.get_word(idx) >> offset,
 
             // Possibly split between neighbour elements
             _ => {
                 // Number of bits to take from the next element
                 let remainder = actual_length + offset - 64;
 
-                let lsb = self.state[idx] >> offset;
+                let lsb = self.get_word(idx) >> offset;
 
-                if self.state.len() == idx + 1 {
-                    // If there is no next element, assume it is zero
-                    lsb
-                } else {
-                    // Otherwise get the remaining bits and assemble the response
-                    let msb = self.state[idx + 1] & ((1 << remainder) - 1);
-                    (msb << (64 - offset)) | lsb
-                }
+                // Get the remaining bits (zero if there is no next element) and assemble the response
+                let msb = self.get_word(idx + 1) & ((1 << remainder) - 1);
+                (msb << (64 - offset)) | lsb
             }
         }
     }
@@ -216,7 +271,7 @@ impl DenseBitSetExtended {
     pub fn insert(&mut self, other: &Self, position: usize, length: usize) {
         let l = (length >> 6) + 1;
         let size_before_insertion = self.size;
-        if length % 64 == 0 {
+        if length & 63 == 0 {
             for i in 0..l {
                 self.insert_u64(other.state[i], position + i * 64, 64);
             }
@@ -232,6 +287,8 @@ impl DenseBitSetExtended {
 
     /// Inserts a `length`-bit integer as a bitset at the given `position`.
     ///
+    /// Note that `value` is truncated to its first `length` bits (little endian convention).
+    ///
     /// # Example
     /// ```
     /// # use rust_dense_bitset::DenseBitSetExtended;
@@ -242,6 +299,13 @@ impl DenseBitSetExtended {
         let idx = position >> 6;
         let offset = position % 64;
 
+        // Truncate `value` to `length` bits so that no extra bit leaks into the neighbours
+        let value = if length < 64 {
+            value & ((1 << length) - 1)
+        } else {
+            value
+        };
+
         // First, resize the bitset if necessary
         if 1 + ((position + length - 1) >> 6) > self.state.len() {
             // We need to extend the bitset to accomodate this insertion
@@ -259,7 +323,7 @@ impl DenseBitSetExtended {
             self.state[idx] = value;
         } else if offset + length - 1 < 64 {
             // Easy case: inserting fewer than 64 bits in an u64
-            let mut u = u64::max_value();
+            let mut u = u64::MAX;
             u ^= ((1 << length) - 1) << offset;
             self.state[idx] &= u;
             self.state[idx] |= value << offset;
@@ -268,10 +332,10 @@ impl DenseBitSetExtended {
             // two segments, and perform the insertion
 
             let lsb = (value & ((1 << (64 - offset)) - 1)) << offset;
-            let mask_lsb = u64::max_value() >> (64 - offset);
+            let mask_lsb = u64::MAX >> (64 - offset);
 
             let msb = value >> (64 - offset);
-            let mask_msb = u64::max_value() << ((position + length) % 64);
+            let mask_msb = u64::MAX << ((position + length) % 64);
 
             self.state[idx] = (self.state[idx] & mask_lsb) | lsb;
             self.state[idx + 1] = (self.state[idx + 1] & mask_msb) | msb;
@@ -391,8 +455,8 @@ impl DenseBitSetExtended {
     /// println!("{}", dbs.first_set());
     /// ```
     pub fn first_set(&self) -> usize {
-        for i in 0..self.state.len() {
-            let cur = self.state[i];
+        for i in 0..min(self.state.len(), self.num_words()) {
+            let cur = self.masked_word(i);
             if cur != 0 {
                 return i * 64 + (cur.trailing_zeros() as usize);
             }
@@ -400,190 +464,1764 @@ impl DenseBitSetExtended {
         self.size
     }
 
-    fn get(&self, index: usize) -> u64 {
-        match index {
-            u if u < self.state.len() => self.state[u],
-            _ => 0,
+    /// Returns the position of the last set bit (little endian convention), or the size of the
+    /// bitset if no bit is set (matching `first_set`).
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbs = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(256)) << 12;
+    /// assert_eq!(dbs.last_set(), 20);
+    /// ```
+    pub fn last_set(&self) -> usize {
+        for i in (0..min(self.state.len(), self.num_words())).rev() {
+            let cur = self.masked_word(i);
+            if cur != 0 {
+                return i * 64 + 63 - (cur.leading_zeros() as usize);
+            }
         }
+        self.size
     }
-}
 
-/// This is an extended implementation of the `BitSet` trait. It dynamically resizes the bitset as necessary
-/// to accomodate growing or shrinking operations (e.g. left shifts) and is only limited by available memory.
-/// In practice however, we (arbitrarily) limited allocation to 64000 bits.
-///
-/// Note: The `BitSet` trait must be in scope in order to use methods from this trait.
-///
-/// Note: The `Copy` trait cannot be implemented for `DenseBitSetExtended` (for the same reasons avec `Vec`).
-impl BitSet for DenseBitSetExtended {
-    /// Sets the bit at index `position` to `value`.
-    fn set_bit(&mut self, position: usize, value: bool) {
-        let idx = position >> 6;
-        let offset = position % 64;
+    /// Returns `true` if the bitset holds the same value as the given `DenseBitSet`, i.e. if its
+    /// first 64 bits match `dbs` and all the remaining bits are zero.
+    ///
+    /// No allocation is performed.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbs = DenseBitSet::from_integer(0b111000111);
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(dbs);
+    /// assert!(dbse.equals_dense(dbs));
+    /// ```
+    pub fn equals_dense(&self, dbs: DenseBitSet) -> bool {
+        // Note: bits beyond the size, if any, are ignored
+        self.masked_word(0) == dbs.to_integer()
+            && (1..self.num_words()).all(|i| self.masked_word(i) == 0)
+    }
 
+    /// Constructs a `DenseBitSetExtended` from a sorted slice of set bit positions.
+    ///
+    /// The size of the resulting bitset is one past the largest position.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSetExtended};
+    /// let bs = DenseBitSetExtended::from_sorted_positions(&[1, 5, 130]);
+    /// assert!(bs.get_bit(130));
+    /// assert_eq!(bs.get_size(), 131);
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if a position exceeds the size limit enforced by `set_bit`. In debug
+    /// builds, it also panics if `positions` is not sorted in increasing order.
+    pub fn from_sorted_positions(positions: &[u32]) -> Self {
+        debug_assert!(
+            positions.windows(2).all(|w| w[0] <= w[1]),
+            "Positions must be sorted in increasing order."
+        );
+        let size = match positions.last() {
+            Some(&p) => p as usize + 1,
+            None => return Self::new(),
+        };
         assert!(
-            idx < 1000,
+            (size - 1) >> 6 < 1000,
             "(Temporary?) We don't allow bitsets larger than 64k for now."
         );
 
-        if idx >= self.state.len() {
-            if value {
-                // This triggers a resize, we only do it if we need to insert a 1
-                for _ in 0..=(idx - self.state.len()) {
-                    self.state.push(0);
-                }
-                self.state[idx] |= 1 << offset
-            }
-        // Note: To insert a zero, we do nothing, as the value is zero by default
-        } else if value {
-            self.state[idx] |= 1 << offset
-        } else {
-            self.state[idx] &= !(1 << offset)
-        }
-        if position >= self.size {
-            self.size = position + 1;
+        let mut state = vec![0; 1 + ((size - 1) >> 6)];
+        for &p in positions {
+            state[(p >> 6) as usize] |= 1 << (p % 64);
         }
+        Self { state, size }
     }
 
-    /// Get the bit at index `position`.
-    fn get_bit(&self, position: usize) -> bool {
-        if position > self.size {
-            return false;
+    /// Returns the positions of the set bits, sorted in increasing order.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSetExtended};
+    /// let mut bs = DenseBitSetExtended::new();
+    /// bs.set_bit(130, true);
+    /// bs.set_bit(5, true);
+    /// assert_eq!(bs.to_sorted_positions(), vec![5, 130]);
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if a set bit's position does not fit in a `u32`.
+    pub fn to_sorted_positions(&self) -> Vec<u32> {
+        let mut positions = Vec::with_capacity(self.get_weight() as usize);
+        for i in 0..min(self.state.len(), self.num_words()) {
+            // Only the set bits are visited, empty words are skipped
+            let mut w = self.masked_word(i);
+            if w == 0 {
+                continue;
+            }
+            let base = u32::try_from(i * 64).expect("Bit positions must fit in a u32.");
+            for _ in 0..w.count_ones() {
+                positions.push(base + w.trailing_zeros());
+                w &= w - 1;
+            }
         }
-
-        let idx = position >> 6;
-        let offset = position % 64;
-
-        (self.state[idx] >> offset) & 1 == 1
+        positions
     }
 
-    /// Returns the bitset's Hamming weight (in other words, the number of bits set to true).
-    fn get_weight(&self) -> u32 {
-        let mut hw = 0;
-        for &s in &self.state {
-            hw += s.count_ones();
+    /// Returns the cumulative Hamming weights at word boundaries.
+    ///
+    /// Element `i` is the number of set bits in words `0..i` (i.e. below position `64 * i`);
+    /// the returned vector has one more element than there are words, the last one being the
+    /// bitset's total weight. This is the backing array for constant time rank queries.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSetExtended};
+    /// let mut bs = DenseBitSetExtended::new();
+    /// bs.set_bit(3, true);
+    /// bs.set_bit(70, true);
+    /// bs.set_bit(71, true);
+    /// assert_eq!(bs.word_prefix_counts(), vec![0, 1, 3]);
+    /// ```
+    pub fn word_prefix_counts(&self) -> Vec<u32> {
+        let l = self.num_words();
+        let mut counts = Vec::with_capacity(l + 1);
+        let mut acc = 0;
+        counts.push(acc);
+        // Note: bits beyond the size, if any, are ignored
+        for i in 0..l {
+            acc += self.masked_word(i).count_ones();
+            counts.push(acc);
         }
-        hw
+        counts
     }
 
-    /// This resets the bitset to its empty state.
-    fn reset(&mut self) {
-        self.state = vec![];
-        self.size = 0
+    /// Returns the 64-bit word at the given `index` (little endian convention), that is
+    /// bits `64 * index` to `64 * index + 63`.
+    ///
+    /// Words beyond the allocated storage are zero.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(42));
+    /// assert_eq!(dbse.get_word(0), 42);
+    /// assert_eq!(dbse.get_word(12), 0);
+    /// ```
+    pub fn get_word(&self, index: usize) -> u64 {
+        match index {
+            u if u < self.state.len() => self.state[u],
+            _ => 0,
+        }
     }
 
-    /// Returns a representation of the bitset as a `String`.
-    fn to_string(self) -> String {
-        if self.state.is_empty() {
-            return format!("{:064b}", 0);
-        }
+    /// Sets the 64-bit word at the given `index` to `value` (little endian convention).
+    ///
+    /// The bitset is extended as necessary, and its size becomes at least `64 * (index + 1)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSetExtended};
+    /// let mut dbse = DenseBitSetExtended::new();
+    /// dbse.set_word(2, 1);
+    /// assert!(dbse.get_bit(128));
+    /// assert_eq!(dbse.get_size(), 192);
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if the bitset would exceed the size limit enforced by `set_bit`.
+    pub fn set_word(&mut self, index: usize, value: u64) {
+        // Note: saturating keeps huge indices within the size limit check
+        self.ensure_size(index.saturating_add(1).saturating_mul(64));
+        self.state[index] = value;
+    }
 
-        let mut bss = vec![];
+    /// Returns an iterator over the maximal runs of set bits, as `(start, length)` pairs in
+    /// increasing order of `start`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b1110011));
+    /// let runs: Vec<(usize, usize)> = dbse.iter_runs().collect();
+    /// assert_eq!(runs, vec![(0, 2), (4, 3)]);
+    /// ```
+    pub fn iter_runs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let mut position = 0;
+        std::iter::from_fn(move || {
+            let start = self.find_next(position, true)?;
+            let end = self.find_next(start, false).unwrap_or(self.size);
+            position = end;
+            Some((start, end - start))
+        })
+    }
 
-        if self.size % 64 == 0 {
-            for s in self.state {
-                bss.push(format!("{:064b}", s));
+    /// Returns the first position at or after `from` (and below the size) whose bit is `value`.
+    ///
+    /// Whole words are skipped at once using `trailing_zeros`.
+    fn find_next(&self, from: usize, value: bool) -> Option<usize> {
+        if from >= self.size {
+            return None;
+        }
+        // Looking for a zero is looking for a one in the inverted word
+        let flip = if value { 0 } else { u64::MAX };
+        let mut idx = from >> 6;
+        let mut w = (self.get_word(idx) ^ flip) & (u64::MAX << (from % 64));
+        loop {
+            if w != 0 {
+                let p = idx * 64 + w.trailing_zeros() as usize;
+                return if p < self.size { Some(p) } else { None };
             }
-        } else {
-            for i in 0..self.state.len() - 1 {
-                bss.push(format!("{:064b}", self.state[i]));
+            idx += 1;
+            if idx * 64 >= self.size {
+                return None;
             }
-            bss.push(format!(
-                "{:064b}",
-                self.state[self.state.len() - 1] & ((1 << (self.size % 64)) - 1)
-            ));
+            w = self.get_word(idx) ^ flip;
         }
-        bss.reverse();
-        bss.join("")
     }
-}
 
-impl fmt::Debug for DenseBitSetExtended {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut bss = String::new();
+    /// Extends the bitset with zero words so that it spans at least `num_words` 64-bit words,
+    /// and sets its size to at least `64 * num_words` bits.
+    ///
+    /// Unlike `with_capacity`, this changes the size of the bitset. Existing bits are left untouched.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let mut bs = DenseBitSetExtended::new();
+    /// bs.grow_words(3);
+    /// assert_eq!(bs.get_size(), 192);
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if the bitset would exceed the size limit enforced by `set_bit`.
+    pub fn grow_words(&mut self, num_words: usize) {
+        // Note: saturating keeps huge word counts within the size limit check
+        self.ensure_size(num_words.saturating_mul(64));
+    }
 
-        for i in 0..self.state.len() {
-            for j in 0..64 {
-                bss += if self.get_bit((self.state.len() - i - 1) * 64 + (63 - j)) {
-                    "1"
-                } else {
-                    "0"
-                };
-            }
-        }
-        write!(f, "0b{}", bss)
+    /// Returns the number of 64-bit words needed to hold `size` bits.
+    fn num_words(&self) -> usize {
+        (self.size + 63) >> 6
     }
-}
 
-impl PartialEq for DenseBitSetExtended {
-    fn eq(&self, other: &Self) -> bool {
-        if self.size != other.size {
-            return false;
-        }
-        for i in 0..self.state.len() {
-            if self.state[i] != other.state[i] {
-                return false;
-            }
+    /// Returns the word at `index` with the bits beyond the size of the bitset cleared.
+    fn masked_word(&self, index: usize) -> u64 {
+        let start = index * 64;
+        if start >= self.size {
+            0
+        } else if self.size - start >= 64 {
+            self.get_word(index)
+        } else {
+            self.get_word(index) & ((1 << (self.size - start)) - 1)
         }
-        true
     }
-}
 
-impl Hash for DenseBitSetExtended {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        for s in &self.state {
-            s.hash(state);
+    /// Writes the bitset to `w`: its size as a little endian `u64`, followed by its words
+    /// as little endian `u64`s.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(42));
+    /// let mut buffer = vec![];
+    /// dbse.write_to(&mut buffer).unwrap();
+    /// assert_eq!(buffer.len(), 16);
+    /// ```
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&(self.size as u64).to_le_bytes())?;
+        for i in 0..self.num_words() {
+            w.write_all(&self.masked_word(i).to_le_bytes())?;
         }
+        Ok(())
     }
-}
-
-impl Eq for DenseBitSetExtended {}
 
-impl Not for DenseBitSetExtended {
-    type Output = Self;
-    fn not(self) -> Self {
-        let l = self.state.len();
-        let mut inv = Self {
-            state: Vec::with_capacity(l),
-            size: self.size,
-        };
-        for i in 0..l - 1 {
-            inv.state.push(!self.state[i])
+    /// Reads a bitset from `r`, in the format produced by `write_to`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(42));
+    /// let mut buffer = vec![];
+    /// dbse.write_to(&mut buffer).unwrap();
+    /// let read = DenseBitSetExtended::read_from(&mut buffer.as_slice()).unwrap();
+    /// assert_eq!(read, dbse);
+    /// ```
+    ///
+    /// Bits of the last word beyond the size are cleared.
+    ///
+    /// # Errors
+    /// This function returns an error if reading fails, in particular if the input is truncated.
+    /// An error of kind `InvalidData` is returned if the size read is too large to be represented.
+    pub fn read_from<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut buf = [0; 8];
+        r.read_exact(&mut buf)?;
+        let invalid_size = || io::Error::new(io::ErrorKind::InvalidData, "Invalid bitset size.");
+        let size = usize::try_from(u64::from_le_bytes(buf)).map_err(|_| invalid_size())?;
+        let num_words = size.checked_add(63).ok_or_else(invalid_size)? >> 6;
+
+        // Note: the storage grows as words are read, so that a corrupt size cannot trigger a
+        // huge allocation
+        let mut state = vec![];
+        for _ in 0..num_words {
+            r.read_exact(&mut buf)?;
+            state.push(u64::from_le_bytes(buf));
         }
-        if self.size % 64 == 0 {
-            inv.state.push(!self.state[l - 1]);
-        } else {
-            inv.state
-                .push((!self.state[l - 1]) & ((1 << (self.size % 64)) - 1));
+        let mut dbse = Self { state, size };
+        if num_words > 0 {
+            dbse.state[num_words - 1] = dbse.masked_word(num_words - 1);
         }
-        inv
+        Ok(dbse)
     }
-}
-
-impl BitAnd for DenseBitSetExtended {
-    type Output = Self;
-    fn bitand(self, rhs: Self) -> Self {
-        let l = min(self.state.len(), rhs.state.len());
-        let mut v = Vec::with_capacity(l);
 
-        // Note: there is no need to go further because x & 0 == 0
+    /// Returns the bitwise majority of three bitsets: each bit is set if and only if it is set in
+    /// at least two of `a`, `b` and `c`.
+    ///
+    /// The size of the result is the largest of the three sizes.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let a = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b1100));
+    /// let b = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b1010));
+    /// let c = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b0110));
+    /// let m = DenseBitSetExtended::majority(&a, &b, &c);
+    /// assert_eq!(m.extract_u64(0, 4), 0b1110);
+    /// ```
+    pub fn majority(a: &Self, b: &Self, c: &Self) -> Self {
+        let size = max(a.size, max(b.size, c.size));
+        let l = (size + 63) >> 6;
+        let mut state = Vec::with_capacity(l);
         for i in 0..l {
-            v.push(self.state[i] & rhs.state[i])
-        }
-
-        Self {
-            state: v,
-            size: min(self.size, rhs.size),
+            let (x, y, z) = (a.masked_word(i), b.masked_word(i), c.masked_word(i));
+            state.push((x & y) | (y & z) | (x & z));
         }
+        Self { state, size }
     }
-}
 
-impl BitAndAssign for DenseBitSetExtended {
-    fn bitand_assign(&mut self, rhs: Self) {
-        // Note: there is no need to go further because x & 0 == 0
-        let l = min(self.state.len(), rhs.state.len());
-        for i in 0..l {
-            self.state[i] &= rhs.state[i];
+    /// Returns the XOR of all the words of the bitset (bits beyond the size are ignored).
+    ///
+    /// This provides a cheap 64-bit fingerprint, e.g. for bucketing. Note that it is not
+    /// collision-resistant: for instance, any two bitsets differing by two identical words fold
+    /// to the same value.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(42));
+    /// assert_eq!(dbse.fold_xor(), 42);
+    /// ```
+    pub fn fold_xor(&self) -> u64 {
+        (0..self.num_words()).fold(0, |acc, i| acc ^ self.masked_word(i))
+    }
+
+    /// Returns the proportion of bits set to `true`, between 0.0 and 1.0 (0.0 for an empty bitset).
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0xffff));
+    /// assert_eq!(dbse.density(), 0.25);
+    /// ```
+    pub fn density(&self) -> f64 {
+        if self.size == 0 {
+            return 0.0;
+        }
+        f64::from(self.get_weight()) / self.size as f64
+    }
+
+    /// Returns the lowest position at which the two bitsets differ, or `None` if all their bits match.
+    ///
+    /// Bits beyond the size of a bitset are considered to be zero.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSet, DenseBitSetExtended};
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(42));
+    /// let mut other = dbse.clone();
+    /// assert_eq!(dbse.first_difference(&other), None);
+    /// other.set_bit(70, true);
+    /// assert_eq!(dbse.first_difference(&other), Some(70));
+    /// ```
+    pub fn first_difference(&self, other: &Self) -> Option<usize> {
+        for i in 0..max(self.num_words(), other.num_words()) {
+            let diff = self.masked_word(i) ^ other.masked_word(i);
+            if diff != 0 {
+                return Some(i * 64 + diff.trailing_zeros() as usize);
+            }
+        }
+        None
+    }
+
+    /// Reduces the size of the bitset to one past its highest set bit (zero if no bit is set).
+    ///
+    /// The values of the remaining bits are unchanged. Note that the allocated capacity is not released.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let mut dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(42)) << 100;
+    /// assert_eq!(dbse.get_size(), 164);
+    /// dbse.fit_size();
+    /// assert_eq!(dbse.get_size(), 106);
+    /// ```
+    pub fn fit_size(&mut self) {
+        let mut size = 0;
+        for i in (0..min(self.state.len(), self.num_words())).rev() {
+            let w = self.masked_word(i);
+            if w != 0 {
+                size = i * 64 + 64 - w.leading_zeros() as usize;
+                break;
+            }
+        }
+        self.state.truncate((size + 63) >> 6);
+        self.size = size;
+    }
+
+    /// Returns a bitset of the same size keeping only the `n` lowest set bits.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b110110));
+    /// assert_eq!(dbse.first_n_set(3).extract_u64(0, 6), 0b010110);
+    /// ```
+    pub fn first_n_set(&self, n: usize) -> Self {
+        let l = min(self.state.len(), self.num_words());
+        let mut state = Vec::with_capacity(l);
+        let mut remaining = n;
+        for i in 0..l {
+            let mut w = self.masked_word(i);
+            let weight = w.count_ones() as usize;
+            if weight > remaining {
+                // Keep the `remaining` lowest bits of this word, by clearing the others
+                let mut kept = 0;
+                for _ in 0..remaining {
+                    kept |= w & w.wrapping_neg();
+                    w &= w - 1;
+                }
+                w = kept;
+            }
+            remaining -= w.count_ones() as usize;
+            state.push(w);
+            if remaining == 0 {
+                break;
+            }
+        }
+        Self {
+            state,
+            size: self.size,
+        }
+    }
+
+    /// Toggles the `len` bits starting at position `start`.
+    ///
+    /// See `flip_range` for the same operation on a range given by its start and end positions.
+    ///
+    /// The bitset is extended if `start + len` exceeds its size; the new bits are considered
+    /// to be previously zero, so toggling sets them.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let mut dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b1010));
+    /// dbse.toggle_range(2, 100);
+    /// assert_eq!(dbse.get_size(), 102);
+    /// assert_eq!(dbse.extract_u64(0, 5), 0b10110);
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if `start + len` overflows, or if it exceeds the size limit enforced
+    /// by `set_bit`.
+    pub fn toggle_range(&mut self, start: usize, len: usize) {
+        let end = start
+            .checked_add(len)
+            .expect("The end of the range overflows.");
+        self.ensure_size(end);
+        self.apply_range(start, end, |w, mask| w ^ mask);
+    }
+
+    /// Extends the bitset with zeros so that its size is at least `size` bits, and its storage
+    /// spans its whole size.
+    ///
+    /// Bits beyond the previous size, if any, are cleared first. Growing is subject to the same
+    /// size limit as `set_bit`.
+    fn ensure_size(&mut self, size: usize) {
+        let l = self.num_words();
+        if size <= self.size {
+            if self.state.len() < l {
+                self.state.resize(l, 0);
+            }
+            return;
+        }
+        assert!(
+            (size - 1) >> 6 < 1000,
+            "(Temporary?) We don't allow bitsets larger than 64k for now."
+        );
+        if l < self.state.len() {
+            self.state.truncate(l);
+        }
+        if l > 0 && l <= self.state.len() {
+            self.state[l - 1] = self.masked_word(l - 1);
+        }
+        self.state.resize((size + 63) >> 6, 0);
+        self.size = size;
+    }
+
+    /// Sets the bit at `position` to `true`, growing the bitset if needed.
+    ///
+    /// Unlike `set_bit`, bits beyond the previous size are cleared when growing.
+    fn insert_position(&mut self, position: usize) {
+        // Note: saturating keeps the largest position within the size limit check
+        self.ensure_size(position.saturating_add(1));
+        self.state[position >> 6] |= 1 << (position % 64);
+    }
+
+    /// Replaces each word `w` overlapping the range `start..end` with `f(w, mask)`, where `mask`
+    /// selects the bits of the word that are within the range.
+    ///
+    /// The storage must already span the range.
+    fn apply_range<F: Fn(u64, u64) -> u64>(&mut self, start: usize, end: usize, f: F) {
+        if start >= end {
+            return;
+        }
+        for idx in (start >> 6)..=((end - 1) >> 6) {
+            let mask = Self::range_word_mask(start, end, idx);
+            self.state[idx] = f(self.state[idx], mask);
+        }
+    }
+
+    /// Returns the mask selecting the bits of word `idx` that are within the range `start..end`.
+    ///
+    /// The word must overlap the range.
+    fn range_word_mask(start: usize, end: usize, idx: usize) -> u64 {
+        let lo = max(start, idx * 64) - idx * 64;
+        let hi = min(end, idx * 64 + 64) - idx * 64;
+        if hi - lo == 64 {
+            u64::MAX
+        } else {
+            ((1 << (hi - lo)) - 1) << lo
+        }
+    }
+
+    /// Clears the bits that are not set in `mask`, in place.
+    ///
+    /// Unlike `&=`, `mask` is borrowed and the size of the bitset is preserved. Bits beyond the
+    /// size of `mask` are considered to be zero.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let mut dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b1111));
+    /// let mask = DenseBitSetExtended::from_string(String::from("0110"), 2);
+    /// dbse.mask_with(&mask);
+    /// assert_eq!(dbse.get_word(0), 0b0110);
+    /// assert_eq!(dbse.get_size(), 64);
+    /// ```
+    pub fn mask_with(&mut self, mask: &Self) {
+        for i in 0..self.state.len() {
+            self.state[i] &= mask.masked_word(i);
+        }
+    }
+
+    /// Reverses the order of the 64-bit words of the bitset, leaving the order of the bits within
+    /// each word unchanged.
+    ///
+    /// Note: if the size is not a multiple of 64, it is first rounded up to the next multiple of 64
+    /// (with zeros), so that the last partial word becomes the first word.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let mut dbse = DenseBitSetExtended::new();
+    /// dbse.set_word(0, 1);
+    /// dbse.set_word(1, 2);
+    /// dbse.reverse_word_order();
+    /// assert_eq!(dbse.get_word(0), 2);
+    /// assert_eq!(dbse.get_word(1), 1);
+    /// ```
+    pub fn reverse_word_order(&mut self) {
+        let l = self.num_words();
+        let mut state: Vec<u64> = (0..l).map(|i| self.masked_word(i)).collect();
+        state.reverse();
+        self.state = state;
+        self.size = l * 64;
+    }
+
+    /// Returns the binary representation of the bitset (most significant bit first, `get_size()`
+    /// characters long) with `sep` inserted between groups of `group` bits.
+    ///
+    /// Groups are aligned on the least significant bit, so that only the leftmost group may be shorter.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_string(String::from("1010110011"), 2);
+    /// assert_eq!(dbse.to_grouped_string(4, ' '), "10 1011 0011");
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `group` is zero.
+    pub fn to_grouped_string(&self, group: usize, sep: char) -> String {
+        assert!(group > 0, "Cannot use zero-width groups.");
+        let mut s = String::with_capacity(self.size + self.size / group);
+        for i in (0..self.size).rev() {
+            s.push(if (self.get_word(i >> 6) >> (i % 64)) & 1 == 1 {
+                '1'
+            } else {
+                '0'
+            });
+            if i > 0 && i % group == 0 {
+                s.push(sep);
+            }
+        }
+        s
+    }
+
+    /// Returns an iterator over the positions at which the two bitsets differ, in increasing order,
+    /// each tagged with the `Side` whose bit is set.
+    ///
+    /// The symmetric difference is streamed word by word, without being materialized. Bits beyond
+    /// the size of a bitset are considered to be zero.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSetExtended, Side};
+    /// let mut a = DenseBitSetExtended::new();
+    /// a.set_bit(3, true);
+    /// a.set_bit(5, true);
+    /// let mut b = DenseBitSetExtended::new();
+    /// b.set_bit(5, true);
+    /// b.set_bit(100, true);
+    /// let changes: Vec<(usize, Side)> = a.changed_positions(&b).collect();
+    /// assert_eq!(changes, vec![(3, Side::Left), (100, Side::Right)]);
+    /// ```
+    pub fn changed_positions<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> impl Iterator<Item = (usize, Side)> + 'a {
+        let l = max(self.num_words(), other.num_words());
+        let diff = move |i| self.masked_word(i) ^ other.masked_word(i);
+        Self::set_positions(l, diff).map(move |position| {
+            let side = if self.get_bit(position) {
+                Side::Left
+            } else {
+                Side::Right
+            };
+            (position, side)
+        })
+    }
+
+    /// Returns the length of the common prefix of the two bitsets, that is the number of matching
+    /// bits from position 0 up to their first difference (at most the smallest of the two sizes).
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let a = DenseBitSetExtended::from_string(String::from("110101"), 2);
+    /// let b = DenseBitSetExtended::from_string(String::from("100101"), 2);
+    /// assert_eq!(a.common_prefix_len(&b), 4);
+    /// ```
+    pub fn common_prefix_len(&self, other: &Self) -> usize {
+        let min_size = min(self.size, other.size);
+        match self.first_difference(other) {
+            Some(p) => min(p, min_size),
+            None => min_size,
+        }
+    }
+
+    /// Resizes the bitset to `new_size` bits, without changing its value as an integer.
+    ///
+    /// Growing always succeeds (new bits are zero), within the size limit enforced by `set_bit`.
+    /// Shrinking fails, leaving the bitset unchanged, if a bit at position `new_size` or above is
+    /// set.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let mut dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b1011));
+    /// assert!(dbse.try_resize_value(4).is_ok());
+    /// assert_eq!(dbse.get_size(), 4);
+    /// assert!(dbse.try_resize_value(3).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns an error if shrinking would drop a set bit.
+    #[allow(clippy::result_unit_err)]
+    pub fn try_resize_value(&mut self, new_size: usize) -> Result<(), ()> {
+        if new_size >= self.size {
+            self.ensure_size(new_size);
+            return Ok(());
+        }
+
+        let l = (new_size + 63) >> 6;
+        let offset = new_size % 64;
+        let mut dropped = (l..self.num_words()).any(|i| self.masked_word(i) != 0);
+        if offset != 0 {
+            dropped |= self.masked_word(l - 1) >> offset != 0;
+        }
+        if dropped {
+            return Err(());
+        }
+
+        self.state.truncate(l);
+        if l > 0 && l <= self.state.len() {
+            self.state[l - 1] = self.masked_word(l - 1);
+        }
+        self.size = new_size;
+        Ok(())
+    }
+
+    /// Returns a 64-bit hash of the bitset which is stable across runs, builds and platforms.
+    ///
+    /// The hash is computed with the FNV-1a algorithm over the size (as a little endian `u64`)
+    /// followed by the words of the bitset (as little endian `u64`s, bits beyond the size cleared),
+    /// i.e. over the output of `write_to`. It is not a cryptographic hash.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// assert_eq!(DenseBitSetExtended::new().stable_hash64(), 0xa8c7f832281a39c5);
+    /// ```
+    pub fn stable_hash64(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut feed = |v: u64| {
+            for b in v.to_le_bytes().iter() {
+                hash ^= u64::from(*b);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+        feed(self.size as u64);
+        for i in 0..self.num_words() {
+            feed(self.masked_word(i));
+        }
+        hash
+    }
+
+    /// Returns `true` if the two bitsets have the same value as integers, regardless of their sizes.
+    ///
+    /// Unlike `==`, which also requires the sizes to match, leading zeros are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let a = DenseBitSetExtended::from_string(String::from("101"), 2);
+    /// let b = DenseBitSetExtended::from_string(String::from("0000101"), 2);
+    /// assert!(a != b);
+    /// assert!(a.numeric_eq(&b));
+    /// ```
+    pub fn numeric_eq(&self, other: &Self) -> bool {
+        self.first_difference(other).is_none()
+    }
+
+    /// Partitions the set bits into `parts` bitsets holding contiguous ranges of positions, with
+    /// weights as equal as possible (they differ by at most one).
+    ///
+    /// Each part has the size of the original bitset, and their union is the original bitset.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b1110110));
+    /// let parts = dbse.split_by_weight(2);
+    /// assert_eq!(parts[0].extract_u64(0, 8), 0b0010110);
+    /// assert_eq!(parts[1].extract_u64(0, 8), 0b1100000);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `parts` is zero.
+    pub fn split_by_weight(&self, parts: usize) -> Vec<Self> {
+        assert!(parts > 0, "Cannot split into zero parts.");
+        let positions = self.to_sorted_positions();
+        let (quotient, remainder) = (positions.len() / parts, positions.len() % parts);
+
+        let mut result = Vec::with_capacity(parts);
+        let mut start = 0;
+        for k in 0..parts {
+            let end = start + quotient + if k < remainder { 1 } else { 0 };
+            let chunk = &positions[start..end];
+            let mut state = match chunk.last() {
+                Some(&p) => vec![0; 1 + (p >> 6) as usize],
+                None => vec![],
+            };
+            for &p in chunk {
+                state[(p >> 6) as usize] |= 1 << (p % 64);
+            }
+            result.push(Self {
+                state,
+                size: self.size,
+            });
+            start = end;
+        }
+        result
+    }
+
+    /// Returns an iterator over the positions of the set bits, in increasing order, grouped into
+    /// batches of at most `batch` positions (only the last batch may be smaller).
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b11101));
+    /// let batches: Vec<Vec<usize>> = dbse.iter_ones_chunked(3).collect();
+    /// assert_eq!(batches, vec![vec![0, 2, 3], vec![4]]);
+    /// ```
+    ///
+    /// # Panics
+    /// This function will panic if `batch` is zero.
+    pub fn iter_ones_chunked(&self, batch: usize) -> impl Iterator<Item = Vec<usize>> + '_ {
+        assert!(batch > 0, "Cannot use zero-sized batches.");
+        let mut position = 0;
+        std::iter::from_fn(move || {
+            let mut chunk = Vec::with_capacity(batch);
+            while chunk.len() < batch {
+                match self.find_next(position, true) {
+                    Some(p) => {
+                        chunk.push(p);
+                        position = p + 1;
+                    }
+                    None => break,
+                }
+            }
+            if chunk.is_empty() {
+                None
+            } else {
+                Some(chunk)
+            }
+        })
+    }
+
+    /// Appends a bit with the given `value` at position `get_size()`, growing the bitset by one bit.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSetExtended};
+    /// let mut dbse = DenseBitSetExtended::new();
+    /// dbse.push_bit(true);
+    /// dbse.push_bit(false);
+    /// assert_eq!(dbse.get_size(), 2);
+    /// assert!(dbse.get_bit(0));
+    /// ```
+    pub fn push_bit(&mut self, value: bool) {
+        let position = self.size;
+        self.set_bit(position, value);
+    }
+
+    /// Removes the highest bit (at position `get_size() - 1`) and returns its value, shrinking the
+    /// bitset by one bit, or returns `None` if the bitset is empty.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let mut dbse = DenseBitSetExtended::new();
+    /// dbse.push_bit(true);
+    /// dbse.push_bit(false);
+    /// assert_eq!(dbse.pop_bit(), Some(false));
+    /// assert_eq!(dbse.pop_bit(), Some(true));
+    /// assert_eq!(dbse.pop_bit(), None);
+    /// ```
+    pub fn pop_bit(&mut self) -> Option<bool> {
+        if self.size == 0 {
+            return None;
+        }
+        let position = self.size - 1;
+        let idx = position >> 6;
+        let offset = position % 64;
+
+        let value = (self.get_word(idx) >> offset) & 1 == 1;
+        if idx < self.state.len() {
+            self.state[idx] &= !(1 << offset);
+        }
+        self.size = position;
+        Some(value)
+    }
+
+    /// Returns the bitwise selection between `a` and `b` according to `mask`: each bit is taken
+    /// from `a` where `mask` is set, and from `b` where it is not (i.e. `(a & mask) | (b & !mask)`).
+    ///
+    /// The size of the result is the largest of the three sizes; missing bits are considered to be zero.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let mask = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b1100));
+    /// let a = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b1010));
+    /// let b = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(0b0101));
+    /// let s = DenseBitSetExtended::bitwise_select(&mask, &a, &b);
+    /// assert_eq!(s.extract_u64(0, 4), 0b1001);
+    /// ```
+    pub fn bitwise_select(mask: &Self, a: &Self, b: &Self) -> Self {
+        let size = max(mask.size, max(a.size, b.size));
+        let l = (size + 63) >> 6;
+        let mut state = Vec::with_capacity(l);
+        for i in 0..l {
+            let m = mask.masked_word(i);
+            state.push((a.masked_word(i) & m) | (b.masked_word(i) & !m));
+        }
+        Self { state, size }
+    }
+
+    /// Returns the decimal representation of the bitset, read as an unsigned integer
+    /// (little endian convention, bits beyond the size are ignored).
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbs = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(1234567890)) << 64;
+    /// assert_eq!(dbs.to_decimal_string(), "22773757908449605611411210240");
+    /// assert_eq!(DenseBitSetExtended::new().to_decimal_string(), "0");
+    /// ```
+    pub fn to_decimal_string(&self) -> String {
+        // Largest power of ten fitting in a u64
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        let mut words: Vec<u64> = (0..self.num_words()).map(|i| self.masked_word(i)).collect();
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        if words.is_empty() {
+            return String::from("0");
+        }
+
+        // Repeatedly divide by 10^19, collecting the remainders (least significant first)
+        let mut chunks = vec![];
+        while !words.is_empty() {
+            let mut rem: u128 = 0;
+            for w in words.iter_mut().rev() {
+                let cur = (rem << 64) | u128::from(*w);
+                *w = (cur / u128::from(CHUNK)) as u64;
+                rem = cur % u128::from(CHUNK);
+            }
+            chunks.push(rem as u64);
+            while words.last() == Some(&0) {
+                words.pop();
+            }
+        }
+
+        let mut s = chunks.pop().unwrap().to_string();
+        for c in chunks.iter().rev() {
+            s.push_str(&format!("{:019}", c));
+        }
+        s
+    }
+
+    /// Returns a lazy iterator over the positions of the set bits, in ascending order.
+    ///
+    /// The words are scanned one at a time, so runs of zeros are skipped cheaply.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSetExtended};
+    /// let mut dbse = DenseBitSetExtended::new();
+    /// dbse.set_bit(3, true);
+    /// dbse.set_bit(70, true);
+    /// assert_eq!(dbse.iter_ones().collect::<Vec<_>>(), vec![3, 70]);
+    /// ```
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        Self::set_positions(self.num_words(), move |i| self.masked_word(i))
+    }
+
+    /// Returns a lazy iterator over the positions set in both `self` and `other`, in ascending
+    /// order. This is equivalent to `(self & other).iter_ones()` without allocating the
+    /// intersection.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let a = DenseBitSetExtended::from_sorted_positions(&[1, 5, 70, 100]);
+    /// let b = DenseBitSetExtended::from_sorted_positions(&[5, 6, 100]);
+    /// assert_eq!(a.common_positions(&b).collect::<Vec<_>>(), vec![5, 100]);
+    /// ```
+    pub fn common_positions<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = usize> + 'a {
+        let l = min(self.num_words(), other.num_words());
+        Self::set_positions(l, move |i| self.masked_word(i) & other.masked_word(i))
+    }
+
+    /// Returns a lazy iterator over the positions of the set bits of the word sequence `word(0)`,
+    /// ..., `word(num_words - 1)`, in ascending order.
+    fn set_positions<F: Fn(usize) -> u64>(
+        num_words: usize,
+        word: F,
+    ) -> impl Iterator<Item = usize> {
+        let mut idx = 0;
+        let mut w = 0;
+        std::iter::from_fn(move || {
+            while w == 0 {
+                if idx >= num_words {
+                    return None;
+                }
+                w = word(idx);
+                idx += 1;
+            }
+            let position = (idx - 1) * 64 + w.trailing_zeros() as usize;
+            // Clear the lowest set bit
+            w &= w - 1;
+            Some(position)
+        })
+    }
+
+    /// Returns the canonical form of the bitset, along with the number of leading zero bits
+    /// trimmed from it.
+    ///
+    /// In the canonical form, the size is one past the highest set bit (zero if no bit is set),
+    /// there are no zero words beyond it, and the bits beyond the size in the top word are cleared.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbse = DenseBitSetExtended::from_dense_bitset(DenseBitSet::from_integer(42)) << 100;
+    /// let (normalized, trimmed) = dbse.normalized();
+    /// assert_eq!(normalized.get_size(), 106);
+    /// assert_eq!(trimmed, 58);
+    /// ```
+    pub fn normalized(&self) -> (Self, usize) {
+        let mut normalized = Self {
+            state: (0..self.num_words()).map(|i| self.masked_word(i)).collect(),
+            size: self.size,
+        };
+        normalized.fit_size();
+        let l = normalized.state.len();
+        if l > 0 {
+            normalized.state[l - 1] = normalized.masked_word(l - 1);
+        }
+        let trimmed = self.size - normalized.size;
+        (normalized, trimmed)
+    }
+
+    /// Returns the bitset packed into `ceil(size / 8)` bytes, little endian: bit 0 is the least
+    /// significant bit of byte 0. Bits of the last byte beyond the size are zero.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_string(String::from("110000000011"), 2);
+    /// assert_eq!(dbse.to_bytes(), vec![0x03, 0x0c]);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = (0..self.num_words())
+            .flat_map(|i| self.masked_word(i).to_le_bytes().to_vec())
+            .collect();
+        bytes.truncate((self.size + 7) >> 3);
+        bytes
+    }
+
+    /// Builds a bitset of size `8 * bytes.len()` from bytes packed little endian: bit 0 is the least
+    /// significant bit of byte 0.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_bytes(&[0x03, 0x0c]);
+    /// assert_eq!(dbse.get_size(), 16);
+    /// assert_eq!(dbse.extract_u64(0, 16), 0x0c03);
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let state = bytes
+            .chunks(8)
+            .map(|chunk| {
+                let mut word = [0; 8];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u64::from_le_bytes(word)
+            })
+            .collect();
+        Self {
+            state,
+            size: bytes.len() * 8,
+        }
+    }
+
+    /// Returns the Hamming weight of each non-overlapping block of `window` bits, starting from
+    /// bit 0. The last block is shorter if `window` does not divide the size.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_string(String::from("1110001011"), 2);
+    /// assert_eq!(dbse.window_popcounts(4), vec![3, 1, 2]);
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if `window` is zero.
+    pub fn window_popcounts(&self, window: usize) -> Vec<u32> {
+        assert!(window > 0, "Cannot use zero-sized windows.");
+        (0..self.size)
+            .step_by(window)
+            .map(|start| self.count_ones_in(start, min(start + window, self.size)))
+            .collect()
+    }
+
+    /// Returns the number of set bits in the range `start..end`, which must lie within the size.
+    fn count_ones_in(&self, start: usize, end: usize) -> u32 {
+        if start >= end {
+            return 0;
+        }
+        let mut count = 0;
+        for idx in (start >> 6)..=((end - 1) >> 6) {
+            count += (self.get_word(idx) & Self::range_word_mask(start, end, idx)).count_ones();
+        }
+        count
+    }
+
+    /// Returns the number of bits set to `false` within the size of the bitset, i.e.
+    /// `size - get_weight()`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_string(String::from("1001011"), 2);
+    /// assert_eq!(dbse.count_zeros(), 3);
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if the size of the bitset does not fit in a `u32`.
+    pub fn count_zeros(&self) -> u32 {
+        u32::try_from(self.size).expect("The size of the bitset must fit in a u32.")
+            - self.get_weight()
+    }
+
+    /// Builds a bitset from 64-bit words (little endian convention: the first word holds bits 0 to
+    /// 63). The size of the bitset is 64 times the number of words.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_words_iter(vec![42, 1]);
+    /// assert_eq!(dbse.get_size(), 128);
+    /// assert_eq!(dbse.get_word(1), 1);
+    /// ```
+    pub fn from_words_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
+        let state: Vec<u64> = iter.into_iter().collect();
+        let size = state.len() * 64;
+        Self { state, size }
+    }
+
+    /// Returns the position of the first set bit at or after `from`, if any.
+    ///
+    /// Words without any set bit are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_sorted_positions(&[3, 70, 1234]);
+    /// assert_eq!(dbse.next_set_bit(4), Some(70));
+    /// assert_eq!(dbse.next_set_bit(1235), None);
+    /// ```
+    pub fn next_set_bit(&self, from: usize) -> Option<usize> {
+        self.find_next(from, true)
+    }
+
+    /// Returns the position of the last set bit at or before `from`, if any.
+    ///
+    /// Words without any set bit are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_sorted_positions(&[3, 70, 1234]);
+    /// assert_eq!(dbse.prev_set_bit(1000), Some(70));
+    /// assert_eq!(dbse.prev_set_bit(2), None);
+    /// ```
+    pub fn prev_set_bit(&self, from: usize) -> Option<usize> {
+        if self.size == 0 {
+            return None;
+        }
+        let from = min(from, self.size - 1);
+        let mut idx = from >> 6;
+        let mut w = self.masked_word(idx) & (u64::MAX >> (63 - from % 64));
+        loop {
+            if w != 0 {
+                return Some(idx * 64 + 63 - w.leading_zeros() as usize);
+            }
+            if idx == 0 {
+                return None;
+            }
+            idx -= 1;
+            w = self.masked_word(idx);
+        }
+    }
+
+    /// Returns the number of bits on which the bitset agrees with `other`, and the number of bits
+    /// on which they differ.
+    ///
+    /// Bits are compared up to the larger of the two sizes, bits beyond the size of a bitset being
+    /// read as zeros.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let a = DenseBitSetExtended::from_string(String::from("1100"), 2);
+    /// let b = DenseBitSetExtended::from_string(String::from("111010"), 2);
+    /// assert_eq!(a.agreement(&b), (2, 4));
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if the larger of the two sizes does not fit in a `u32`.
+    pub fn agreement(&self, other: &Self) -> (u32, u32) {
+        let size = u32::try_from(max(self.size, other.size))
+            .expect("The size of the bitsets must fit in a u32.");
+        let l = max(self.num_words(), other.num_words());
+        let hamming = (0..l)
+            .map(|i| (self.masked_word(i) ^ other.masked_word(i)).count_ones())
+            .sum::<u32>();
+        (size - hamming, hamming)
+    }
+
+    /// Returns the position of the `n`-th set bit (starting from zero), or `None` if fewer than
+    /// `n + 1` bits are set. This is the `select` operation of succinct data structures.
+    ///
+    /// Whole words are skipped using their Hamming weight.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_sorted_positions(&[3, 70, 1234]);
+    /// assert_eq!(dbse.select(1), Some(70));
+    /// assert_eq!(dbse.select(3), None);
+    /// ```
+    pub fn select(&self, n: usize) -> Option<usize> {
+        let mut remaining = n;
+        for i in 0..min(self.state.len(), self.num_words()) {
+            let mut w = self.masked_word(i);
+            let weight = w.count_ones() as usize;
+            if remaining < weight {
+                for _ in 0..remaining {
+                    // Clear the lowest set bit
+                    w &= w - 1;
+                }
+                return Some(i * 64 + w.trailing_zeros() as usize);
+            }
+            remaining -= weight;
+        }
+        None
+    }
+
+    /// Returns the number of set bits at positions strictly below `position`, that is the whole
+    /// weight of the bitset if `position` is beyond its size.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_sorted_positions(&[3, 70, 1234]);
+    /// assert_eq!(dbse.rank(70), 1);
+    /// assert_eq!(dbse.rank(71), 2);
+    /// assert_eq!(dbse.rank(5000), 3);
+    /// ```
+    pub fn rank(&self, position: usize) -> usize {
+        self.count_ones_in(0, min(position, self.size)) as usize
+    }
+
+    /// Returns the set difference of the two bitsets: the bits set in `self` but not in `other`.
+    ///
+    /// The result has the size of `self`; bits beyond the size of `other` are read as zeros in
+    /// `other`, so they are kept.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let a = DenseBitSetExtended::from_sorted_positions(&[1, 5, 70]);
+    /// let b = DenseBitSetExtended::from_sorted_positions(&[5]);
+    /// assert_eq!(a.difference(&b).iter_ones().collect::<Vec<_>>(), vec![1, 70]);
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        let state = (0..self.num_words())
+            .map(|i| self.masked_word(i) & !other.masked_word(i))
+            .collect();
+        Self {
+            state,
+            size: self.size,
+        }
+    }
+
+    /// Returns the gaps between successive set bits: the first element is the position of the
+    /// first set bit, and each following element is the distance from the previous set bit.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_sorted_positions(&[3, 5, 70]);
+    /// assert_eq!(dbse.to_delta_positions(), vec![3, 2, 65]);
+    /// ```
+    pub fn to_delta_positions(&self) -> Vec<usize> {
+        let mut previous = 0;
+        self.iter_ones()
+            .map(|p| {
+                let delta = p - previous;
+                previous = p;
+                delta
+            })
+            .collect()
+    }
+
+    /// Builds a bitset from the gaps between successive set bits, as returned by
+    /// `to_delta_positions`. The size of the bitset is one past the last set bit.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let dbse = DenseBitSetExtended::from_delta_positions(&[3, 2, 65]);
+    /// assert_eq!(dbse.iter_ones().collect::<Vec<_>>(), vec![3, 5, 70]);
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if a delta other than the first one is zero, if the sum of the deltas
+    /// overflows, or if a position exceeds the size limit enforced by `set_bit`.
+    pub fn from_delta_positions(deltas: &[usize]) -> Self {
+        let mut position: usize = 0;
+        deltas
+            .iter()
+            .enumerate()
+            .map(|(i, &d)| {
+                assert!(i == 0 || d > 0, "Deltas between set bits must be positive.");
+                position = position
+                    .checked_add(d)
+                    .expect("The sum of the deltas overflows.");
+                position
+            })
+            .collect()
+    }
+
+    /// Returns the symmetric difference of the two bitsets: the bits set in exactly one of them.
+    ///
+    /// This is the same as `self ^ other`, without consuming the operands. The result has the
+    /// larger of the two sizes.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let a = DenseBitSetExtended::from_sorted_positions(&[1, 5]);
+    /// let b = DenseBitSetExtended::from_sorted_positions(&[5, 70]);
+    /// assert_eq!(a.symmetric_difference(&b).iter_ones().collect::<Vec<_>>(), vec![1, 70]);
+    /// ```
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        let size = max(self.size, other.size);
+        let state = (0..(size + 63) >> 6)
+            .map(|i| self.masked_word(i) ^ other.masked_word(i))
+            .collect();
+        Self { state, size }
+    }
+
+    /// Returns `true` if every bit set in `self` is also set in `other`, whatever their sizes.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let a = DenseBitSetExtended::from_sorted_positions(&[5]);
+    /// let b = DenseBitSetExtended::from_sorted_positions(&[5, 70]);
+    /// assert!(a.is_subset(&b));
+    /// assert!(!b.is_subset(&a));
+    /// ```
+    pub fn is_subset(&self, other: &Self) -> bool {
+        (0..self.num_words()).all(|i| self.masked_word(i) & !other.masked_word(i) == 0)
+    }
+
+    /// Returns `true` if every bit set in `other` is also set in `self`, whatever their sizes.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let a = DenseBitSetExtended::from_sorted_positions(&[5]);
+    /// let b = DenseBitSetExtended::from_sorted_positions(&[5, 70]);
+    /// assert!(b.is_superset(&a));
+    /// assert!(!a.is_superset(&b));
+    /// ```
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `self & !other` without computing the complement of `other`; this is the same as
+    /// `difference`. The result has the size of `self`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let a = DenseBitSetExtended::from_sorted_positions(&[1, 5, 70]);
+    /// let b = DenseBitSetExtended::from_sorted_positions(&[5]);
+    /// assert_eq!(a.and_not(&b).iter_ones().collect::<Vec<_>>(), vec![1, 70]);
+    /// ```
+    pub fn and_not(&self, other: &Self) -> Self {
+        self.difference(other)
+    }
+
+    /// Clears the bits of `self` which are set in `other`, in place. The size of `self` is unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let mut a = DenseBitSetExtended::from_sorted_positions(&[1, 5, 70]);
+    /// a.and_not_assign(&DenseBitSetExtended::from_sorted_positions(&[5]));
+    /// assert_eq!(a.iter_ones().collect::<Vec<_>>(), vec![1, 70]);
+    /// ```
+    pub fn and_not_assign(&mut self, other: &Self) {
+        for i in 0..min(self.state.len(), min(self.num_words(), other.state.len())) {
+            self.state[i] &= !other.masked_word(i);
+        }
+    }
+
+    /// Returns `true` if the two bitsets have no set bit in common.
+    ///
+    /// Only the words covered by both bitsets are read.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let a = DenseBitSetExtended::from_sorted_positions(&[1, 70]);
+    /// assert!(a.is_disjoint(&DenseBitSetExtended::from_sorted_positions(&[2, 71])));
+    /// assert!(!a.is_disjoint(&DenseBitSetExtended::from_sorted_positions(&[70])));
+    /// ```
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        let l = min(
+            min(self.state.len(), self.num_words()),
+            min(other.state.len(), other.num_words()),
+        );
+        (0..l).all(|i| self.masked_word(i) & other.masked_word(i) == 0)
+    }
+
+    /// Returns the number of bits set in both `self` and `other`, i.e. the weight of `self & other`,
+    /// without allocating the intersection.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let a = DenseBitSetExtended::from_sorted_positions(&[1, 5, 70, 100]);
+    /// let b = DenseBitSetExtended::from_sorted_positions(&[5, 6, 100]);
+    /// assert_eq!(a.intersection_count(&b), 2);
+    /// ```
+    pub fn intersection_count(&self, other: &Self) -> u32 {
+        (0..min(self.num_words(), other.num_words()))
+            .map(|i| (self.masked_word(i) & other.masked_word(i)).count_ones())
+            .sum()
+    }
+
+    /// Returns the intersection count (see `intersection_count`) of `self` with each of `others`.
+    ///
+    /// The words of `self` are only masked once, which makes this faster than calling
+    /// `intersection_count` for each candidate.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// let query = DenseBitSetExtended::from_sorted_positions(&[1, 5, 70]);
+    /// let candidates = vec![
+    ///     DenseBitSetExtended::from_sorted_positions(&[5, 70]),
+    ///     DenseBitSetExtended::from_sorted_positions(&[2]),
+    /// ];
+    /// assert_eq!(query.intersection_counts(&candidates), vec![2, 0]);
+    /// ```
+    pub fn intersection_counts(&self, others: &[Self]) -> Vec<u32> {
+        let words: Vec<u64> = (0..self.num_words()).map(|i| self.masked_word(i)).collect();
+        others
+            .iter()
+            .map(|other| {
+                words
+                    .iter()
+                    .take(other.num_words())
+                    .enumerate()
+                    .map(|(i, &w)| (w & other.masked_word(i)).count_ones())
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Formats the bitset as a hexadecimal integer without leading zeros, most significant
+    /// word first, and pads it according to the formatter's flags.
+    fn fmt_hex(&self, f: &mut fmt::Formatter, upper: bool) -> fmt::Result {
+        let mut words = (0..self.num_words())
+            .rev()
+            .map(|i| self.masked_word(i))
+            .skip_while(|&w| w == 0);
+        let mut s = match words.next() {
+            Some(w) if upper => format!("{:X}", w),
+            Some(w) => format!("{:x}", w),
+            None => String::from("0"),
+        };
+        for w in words {
+            if upper {
+                s.push_str(&format!("{:016X}", w));
+            } else {
+                s.push_str(&format!("{:016x}", w));
+            }
+        }
+        f.pad_integral(true, "0x", &s)
+    }
+
+    /// Returns a parallel iterator over the positions of the set bits. The words of the bitset
+    /// are distributed across threads; the positions are not yielded in any particular order.
+    ///
+    /// This requires the `rayon` feature.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::DenseBitSetExtended;
+    /// use rayon::prelude::*;
+    ///
+    /// let dbse = DenseBitSetExtended::from_sorted_positions(&[3, 70, 1234]);
+    /// let mut positions: Vec<usize> = dbse.par_iter_ones().collect();
+    /// positions.sort();
+    /// assert_eq!(positions, vec![3, 70, 1234]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_ones(&self) -> impl ParallelIterator<Item = usize> + '_ {
+        (0..self.num_words())
+            .into_par_iter()
+            .flat_map_iter(move |idx| {
+                Self::set_positions(1, move |_| self.masked_word(idx)).map(move |p| idx * 64 + p)
+            })
+    }
+
+    /// Sets the bits at positions `start..end` to `value`, one word at a time.
+    ///
+    /// When setting bits to `true`, the bitset is extended if `end` exceeds its size.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSetExtended};
+    /// let mut dbse = DenseBitSetExtended::new();
+    /// dbse.set_range(60, 130, true);
+    /// assert_eq!(dbse.get_size(), 130);
+    /// assert_eq!(dbse.get_weight(), 70);
+    /// dbse.set_range(64, 200, false);
+    /// assert_eq!(dbse.get_size(), 130);
+    /// assert_eq!(dbse.get_weight(), 4);
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if `start > end`, or if `end` exceeds the size limit enforced by
+    /// `set_bit` when setting bits to `true`.
+    pub fn set_range(&mut self, start: usize, end: usize, value: bool) {
+        assert!(start <= end, "The start of a range cannot exceed its end.");
+        if value {
+            self.ensure_size(end);
+            self.apply_range(start, end, |w, mask| w | mask);
+        } else {
+            // Bits beyond the size are zeros already
+            let size = self.size;
+            self.ensure_size(size);
+            self.apply_range(start, min(end, size), |w, mask| w & !mask);
+        }
+    }
+
+    /// Toggles the bits at positions `start..end`, one word at a time.
+    ///
+    /// This is the same as `toggle_range(start, end - start)`, with the `start..end` convention of
+    /// `set_range`.
+    ///
+    /// The bitset is extended if `end` exceeds its size; the new bits are considered to be
+    /// previously zero, so toggling sets them.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{BitSet, DenseBitSetExtended};
+    /// let mut dbse = DenseBitSetExtended::from_sorted_positions(&[0, 63]);
+    /// dbse.flip_range(62, 66);
+    /// assert_eq!(dbse.get_size(), 66);
+    /// assert_eq!(dbse.iter_ones().collect::<Vec<_>>(), vec![0, 62, 64, 65]);
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if `start > end`, or if `end` exceeds the size limit enforced by
+    /// `set_bit`.
+    pub fn flip_range(&mut self, start: usize, end: usize) {
+        assert!(start <= end, "The start of a range cannot exceed its end.");
+        self.toggle_range(start, end - start);
+    }
+
+    /// Inverts every bit of the bitset below its size, in place.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_dense_bitset::{DenseBitSet, DenseBitSetExtended};
+    /// let dbs = DenseBitSet::from_integer(0b1010);
+    /// let mut dbse = DenseBitSetExtended::from_dense_bitset(dbs).subset(0, 6);
+    /// dbse.flip();
+    /// assert_eq!(dbse.get_word(0), 0b110101);
+    /// ```
+    pub fn flip(&mut self) {
+        let l = self.num_words();
+        let mut state: Vec<u64> = (0..l).map(|i| !self.masked_word(i)).collect();
+        if self.size & 63 != 0 {
+            state[l - 1] &= (1 << (self.size % 64)) - 1;
+        }
+        self.state = state;
+    }
+}
+
+/// This is an extended implementation of the `BitSet` trait. It dynamically resizes the bitset as necessary
+/// to accomodate growing or shrinking operations (e.g. left shifts) and is only limited by available memory.
+/// In practice however, we (arbitrarily) limited allocation to 64000 bits.
+///
+/// Note: The `BitSet` trait must be in scope in order to use methods from this trait.
+///
+/// Note: The `Copy` trait cannot be implemented for `DenseBitSetExtended` (for the same reasons avec `Vec`).
+impl BitSet for DenseBitSetExtended {
+    /// Sets the bit at index `position` to `value`.
+    fn set_bit(&mut self, position: usize, value: bool) {
+        let idx = position >> 6;
+        let offset = position % 64;
+
+        assert!(
+            idx < 1000,
+            "(Temporary?) We don't allow bitsets larger than 64k for now."
+        );
+
+        if idx >= self.state.len() {
+            if value {
+                // This triggers a resize, we only do it if we need to insert a 1
+                for _ in 0..=(idx - self.state.len()) {
+                    self.state.push(0);
+                }
+                self.state[idx] |= 1 << offset
+            }
+        // Note: To insert a zero, we do nothing, as the value is zero by default
+        } else if value {
+            self.state[idx] |= 1 << offset
+        } else {
+            self.state[idx] &= !(1 << offset)
+        }
+        if position >= self.size {
+            self.size = position + 1;
+        }
+    }
+
+    /// Get the bit at index `position`.
+    fn get_bit(&self, position: usize) -> bool {
+        if position >= self.size {
+            return false;
+        }
+
+        let idx = position >> 6;
+        let offset = position % 64;
+
+        (self.get_word(idx) >> offset) & 1 == 1
+    }
+
+    /// Returns the bitset's Hamming weight (in other words, the number of bits set to true).
+    fn get_weight(&self) -> u32 {
+        let mut hw = 0;
+        // Note: bits beyond the size, if any, are ignored
+        for i in 0..min(self.state.len(), self.num_words()) {
+            hw += self.masked_word(i).count_ones();
+        }
+        hw
+    }
+
+    /// This resets the bitset to its empty state.
+    fn reset(&mut self) {
+        self.state = vec![];
+        self.size = 0
+    }
+
+    /// Returns a representation of the bitset as a `String`.
+    fn to_string(self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl fmt::Debug for DenseBitSetExtended {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bss = String::new();
+
+        for i in 0..self.state.len() {
+            for j in 0..64 {
+                bss += if self.get_bit((self.state.len() - i - 1) * 64 + (63 - j)) {
+                    "1"
+                } else {
+                    "0"
+                };
+            }
+        }
+        write!(f, "0b{}", bss)
+    }
+}
+
+/// Writes the bitset as a binary string (little endian, with leading zeroes) whose length is
+/// a multiple of 64, like `to_string`.
+impl fmt::Display for DenseBitSetExtended {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.num_words() == 0 {
+            return write!(f, "{:064b}", 0);
+        }
+
+        for i in (0..self.num_words()).rev() {
+            write!(f, "{:064b}", self.masked_word(i))?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the bitset as a hexadecimal integer (bits beyond the size are ignored), honoring the
+/// usual formatting flags.
+impl fmt::LowerHex for DenseBitSetExtended {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_hex(f, false)
+    }
+}
+
+/// Writes the bitset as a hexadecimal integer (bits beyond the size are ignored), honoring the
+/// usual formatting flags.
+impl fmt::UpperHex for DenseBitSetExtended {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_hex(f, true)
+    }
+}
+
+impl PartialEq for DenseBitSetExtended {
+    fn eq(&self, other: &Self) -> bool {
+        if self.size != other.size {
+            return false;
+        }
+        // Note: bits beyond the size, if any, are ignored
+        for i in 0..self.num_words() {
+            if self.masked_word(i) != other.masked_word(i) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl Hash for DenseBitSetExtended {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Note: this must remain consistent with `PartialEq`
+        for i in 0..self.num_words() {
+            self.masked_word(i).hash(state);
+        }
+    }
+}
+
+impl Eq for DenseBitSetExtended {}
+
+impl Not for DenseBitSetExtended {
+    type Output = Self;
+    fn not(self) -> Self {
+        let mut inv = self;
+        inv.flip();
+        inv
+    }
+}
+
+impl BitAnd for DenseBitSetExtended {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self {
+        let size = min(self.size, rhs.size);
+        let l = min((size + 63) >> 6, min(self.state.len(), rhs.state.len()));
+        let mut v = Vec::with_capacity(l);
+
+        // Note: there is no need to go further because x & 0 == 0
+        for i in 0..l {
+            v.push(self.masked_word(i) & rhs.masked_word(i))
+        }
+
+        Self { state: v, size }
+    }
+}
+
+impl BitAndAssign for DenseBitSetExtended {
+    fn bitand_assign(&mut self, rhs: Self) {
+        // Note: words beyond the storage of rhs are read as zeros
+        for i in 0..self.state.len() {
+            self.state[i] &= rhs.get_word(i);
         }
         self.size = min(self.size, rhs.size);
     }
@@ -592,41 +2230,26 @@ impl BitAndAssign for DenseBitSetExtended {
 impl BitOr for DenseBitSetExtended {
     type Output = Self;
     fn bitor(self, rhs: Self) -> Self {
-        let l = max(self.state.len(), rhs.state.len());
+        let size = max(self.size, rhs.size);
+        let l = min((size + 63) >> 6, max(self.state.len(), rhs.state.len()));
         let mut v = Vec::with_capacity(l);
 
+        // Note: words beyond the storage or bits beyond the size of an operand are read as zeros,
+        // and x | 0 == x
         for i in 0..l {
-            if i < self.state.len() && i < rhs.state.len() {
-                // x | y
-                v.push(self.state[i] | rhs.state[i])
-            } else if i >= self.state.len() {
-                // x | 0 == x
-                v.push(rhs.state[i])
-            } else {
-                // x | 0 == x
-                v.push(self.state[i])
-            }
+            v.push(self.masked_word(i) | rhs.masked_word(i))
         }
 
-        Self {
-            state: v,
-            size: max(self.size, rhs.size),
-        }
+        Self { state: v, size }
     }
 }
 
 impl BitOrAssign for DenseBitSetExtended {
     fn bitor_assign(&mut self, rhs: Self) {
-        let l = max(self.state.len(), rhs.state.len());
-        for i in 0..l {
-            if i < self.state.len() && i < rhs.state.len() {
-                // x | y
-                self.state[i] |= rhs.state[i]
-            } else if i >= self.state.len() {
-                // x | 0 == x
-                self.state[i] = rhs.state[i]
-            }
-            // if rhs.state[i] == 0 we do nothing because x | 0 == x
+        self.ensure_size(rhs.size);
+        // Note: bits beyond the size of rhs are read as zeros, and x | 0 == x
+        for i in 0..min(rhs.state.len(), rhs.num_words()) {
+            self.state[i] |= rhs.masked_word(i);
         }
     }
 }
@@ -634,41 +2257,26 @@ impl BitOrAssign for DenseBitSetExtended {
 impl BitXor for DenseBitSetExtended {
     type Output = Self;
     fn bitxor(self, rhs: Self) -> Self {
-        let l = max(self.state.len(), rhs.state.len());
+        let size = max(self.size, rhs.size);
+        let l = min((size + 63) >> 6, max(self.state.len(), rhs.state.len()));
         let mut v = Vec::with_capacity(l);
 
+        // Note: words beyond the storage or bits beyond the size of an operand are read as zeros,
+        // and x ^ 0 == x
         for i in 0..l {
-            if i < self.state.len() && i < rhs.state.len() {
-                // x ^ y
-                v.push(self.state[i] ^ rhs.state[i])
-            } else if i >= self.state.len() {
-                // x ^ 0 == x
-                v.push(rhs.state[i])
-            } else {
-                // x ^ 0 == x
-                v.push(self.state[i])
-            }
+            v.push(self.masked_word(i) ^ rhs.masked_word(i))
         }
 
-        Self {
-            state: v,
-            size: max(self.size, rhs.size),
-        }
+        Self { state: v, size }
     }
 }
 
 impl BitXorAssign for DenseBitSetExtended {
     fn bitxor_assign(&mut self, rhs: Self) {
-        let l = max(self.state.len(), rhs.state.len());
-        for i in 0..l {
-            if i < self.state.len() && i < rhs.state.len() {
-                // x ^ y
-                self.state[i] ^= rhs.state[i]
-            } else if i >= self.state.len() {
-                // x ^ 0 == x
-                self.state[i] = rhs.state[i]
-            }
-            // if rhs.state[i] == 0 we do nothing because x ^ 0 == x
+        self.ensure_size(rhs.size);
+        // Note: bits beyond the size of rhs are read as zeros, and x ^ 0 == x
+        for i in 0..min(rhs.state.len(), rhs.num_words()) {
+            self.state[i] ^= rhs.masked_word(i);
         }
     }
 }
@@ -692,19 +2300,17 @@ impl ShlAssign<usize> for DenseBitSetExtended {
     fn shl_assign(&mut self, rhs: usize) {
         let trailing_zeros = rhs >> 6;
         let actual_shift = rhs % 64;
-        if rhs > (self.state.len() * 64 - self.size) {
-            self.state.push(0);
-        }
-        let l = self.state.len();
-        for i in 0..(l - 1) {
-            self.state[l - i - 1] = (self.state[l - i - 1] << actual_shift)
-                | (self.state[l - i - 2] >> (64 - actual_shift))
-        }
-        self.state[0] <<= actual_shift;
-        for _ in 0..trailing_zeros {
-            self.state.insert(0, 0);
+        let size = self.size + rhs;
+        let mut state = vec![0; (size + 63) >> 6];
+        for i in 0..min(self.state.len(), self.num_words()) {
+            let w = self.masked_word(i);
+            state[i + trailing_zeros] |= w << actual_shift;
+            if actual_shift > 0 && i + trailing_zeros + 1 < state.len() {
+                state[i + trailing_zeros + 1] |= w >> (64 - actual_shift);
+            }
         }
-        self.size += rhs;
+        self.state = state;
+        self.size = size;
     }
 }
 
@@ -735,18 +2341,79 @@ impl ShrAssign<usize> for DenseBitSetExtended {
     fn shr_assign(&mut self, rhs: usize) {
         if rhs >= self.size {
             self.reset();
+            return;
         }
         let to_drop = rhs >> 6;
         let actual_shift = rhs % 64;
-        for _ in 0..to_drop {
-            self.state.remove(0);
+        let size = self.size - rhs;
+        let mut state = vec![0; (size + 63) >> 6];
+        for (i, w) in state.iter_mut().enumerate() {
+            *w = self.masked_word(i + to_drop) >> actual_shift;
+            if actual_shift > 0 {
+                *w |= self.masked_word(i + to_drop + 1) << (64 - actual_shift);
+            }
+        }
+        self.state = state;
+        self.size = size;
+    }
+}
+
+impl<'a> Iterator for Bits<'a> {
+    type Item = bool;
+    fn next(&mut self) -> Option<bool> {
+        if self.position >= self.bitset.size {
+            return None;
+        }
+        let p = self.position;
+        self.position += 1;
+        Some((self.bitset.get_word(p >> 6) >> (p % 64)) & 1 == 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.bitset.size.saturating_sub(self.position);
+        (n, Some(n))
+    }
+}
+
+impl<'a> ExactSizeIterator for Bits<'a> {}
+
+impl<'a> IntoIterator for &'a DenseBitSetExtended {
+    type Item = bool;
+    type IntoIter = Bits<'a>;
+    fn into_iter(self) -> Bits<'a> {
+        Bits {
+            bitset: self,
+            position: 0,
+        }
+    }
+}
+
+impl FromIterator<usize> for DenseBitSetExtended {
+    /// Builds a bitset with the given positions set. Its size is one more than the largest position.
+    ///
+    /// The positions are consumed in a single pass, without being buffered: the storage grows as
+    /// larger positions arrive, rather than being allocated once from the largest position.
+    ///
+    /// This panics if a position exceeds the size limit enforced by `set_bit`.
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut dbse = Self::new();
+        for p in iter {
+            dbse.insert_position(p);
         }
-        let l = self.state.len();
-        for i in 0..(l - 1) {
-            self.state[i] =
-                (self.state[i] >> actual_shift) | (self.state[i + 1] << (64 - actual_shift))
+        dbse
+    }
+}
+
+impl Extend<usize> for DenseBitSetExtended {
+    /// Sets the given positions, growing the bitset if needed.
+    ///
+    /// As with `FromIterator`, the positions are consumed in a single pass, without being buffered:
+    /// the storage grows as larger positions arrive.
+    ///
+    /// This panics if a position exceeds the size limit enforced by `set_bit`.
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for p in iter {
+            self.insert_position(p);
         }
-        self.state[l - 1] >>= actual_shift;
-        self.size -= rhs;
     }
 }